@@ -0,0 +1,225 @@
+use std::{cmp::Reverse, collections::HashSet, sync::Arc};
+
+use ethers::{
+    contract::ContractError,
+    providers::{Http, Middleware, Provider, ProviderError},
+    types::{transaction::eip2718::TypedTransaction, Filter, H160, H256, U256, U64},
+    utils::keccak256,
+};
+use futures::future;
+
+use crate::{abis::YakAdapter, network::Network, query::adapters::Adapter};
+
+pub struct AdapterAdmin {}
+
+#[derive(Clone)]
+pub struct OwnedAdapter {
+    pub adapter: Adapter,
+    pub gas_estimate: U256,
+}
+
+// @dev gas of a single-hop swap through the adapter, intrinsic gas is tx base cost and calldata
+pub struct AdapterGasSample {
+    pub gas_used: U256,
+    pub intrinsic_gas: U256,
+}
+
+impl AdapterGasSample {
+    // @dev still includes router overhead (transfers to adapter, checks), so it's an upper bound
+    pub fn adapter_gas(&self) -> U256 {
+        self.gas_used.saturating_sub(self.intrinsic_gas)
+    }
+}
+
+pub enum AdapterAdminCall {
+    SetSwapGasEstimate(U256),
+    SetAllowances,
+    RevokeAllowance(H160, H160),
+    RecoverERC20(H160, U256),
+    RecoverAVAX(U256),
+    TransferOwnership(H160),
+    RenounceOwnership,
+}
+
+impl std::fmt::Display for AdapterAdminCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdapterAdminCall::SetSwapGasEstimate(estimate) => {
+                write!(f, "setSwapGasEstimate({})", estimate)
+            }
+            AdapterAdminCall::SetAllowances => write!(f, "setAllowances()"),
+            AdapterAdminCall::RevokeAllowance(token, spender) => {
+                write!(f, "revokeAllowance({:?}, {:?})", token, spender)
+            }
+            AdapterAdminCall::RecoverERC20(token, amount) => {
+                write!(f, "recoverERC20({:?}, {})", token, amount)
+            }
+            AdapterAdminCall::RecoverAVAX(amount) => write!(f, "recoverAVAX({})", amount),
+            AdapterAdminCall::TransferOwnership(owner) => {
+                write!(f, "transferOwnership({:?})", owner)
+            }
+            AdapterAdminCall::RenounceOwnership => write!(f, "renounceOwnership()"),
+        }
+    }
+}
+
+impl AdapterAdmin {
+    // @dev public RPCs limit block range of eth_getLogs
+    const LOGS_BLOCK_RANGE: u64 = 2048;
+    const MAX_GAS_SAMPLES: usize = 20;
+    // @dev rough swapNoSplit cost outside the adapter: transferFrom to adapter, balance checks, YakSwap event
+    pub const ROUTER_OVERHEAD_GAS: u64 = 45000;
+
+    // @dev router adapters owned by `owner`, with their current swapGasEstimate.
+    // @dev adapters without owner() (not Ownable) are skipped
+    #[tokio::main]
+    pub async fn get_owned(
+        owner: H160,
+        adapters: Vec<Adapter>,
+        current_network: Arc<Network>,
+    ) -> Result<Vec<OwnedAdapter>, ContractError<Provider<Http>>> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let owned = future::join_all(adapters.into_iter().map(|adapter| {
+            let adapter_contract = YakAdapter::new(adapter.address, provider.clone());
+
+            async move {
+                match adapter_contract.owner().call().await {
+                    Ok(adapter_owner) if adapter_owner == owner => {}
+                    _ => return Ok(None),
+                }
+
+                let gas_estimate = adapter_contract.swap_gas_estimate().call().await?;
+
+                Ok::<Option<OwnedAdapter>, ContractError<Provider<Http>>>(Some(OwnedAdapter {
+                    adapter,
+                    gas_estimate,
+                }))
+            }
+        }))
+        .await;
+
+        owned
+            .into_iter()
+            .filter_map(|owned| owned.transpose())
+            .collect()
+    }
+
+    // @dev receipts of recent txs where the adapter was the only YakAdapterSwap, i.e. single-hop swaps
+    #[tokio::main]
+    pub async fn measure_gas(
+        adapter: H160,
+        blocks: u64,
+        current_network: Arc<Network>,
+    ) -> Result<Vec<AdapterGasSample>, ProviderError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let latest_block = provider.get_block_number().await?.as_u64();
+
+        let swap_topic = H256::from(keccak256("YakAdapterSwap(address,address,uint256,uint256)"));
+
+        let mut swaps: Vec<(U64, H256)> = vec![];
+
+        let mut from_block = latest_block.saturating_sub(blocks);
+
+        while from_block <= latest_block {
+            let to_block = (from_block + Self::LOGS_BLOCK_RANGE - 1).min(latest_block);
+
+            let filter = Filter::new()
+                .from_block(from_block)
+                .to_block(to_block)
+                .address(adapter)
+                .topic0(swap_topic);
+
+            for log in provider.get_logs(&filter).await? {
+                if let (Some(block_number), Some(tx_hash)) =
+                    (log.block_number, log.transaction_hash)
+                {
+                    swaps.push((block_number, tx_hash));
+                }
+            }
+
+            from_block = to_block + 1;
+        }
+
+        let mut seen: HashSet<H256> = HashSet::new();
+
+        swaps.sort_by_key(|(block_number, _)| Reverse(*block_number));
+        swaps.retain(|(_, tx_hash)| seen.insert(*tx_hash));
+        swaps.truncate(Self::MAX_GAS_SAMPLES);
+
+        let samples = future::join_all(swaps.iter().map(|(_, tx_hash)| {
+            let provider = &provider;
+
+            async move {
+                let receipt = provider.get_transaction_receipt(*tx_hash).await.ok()??;
+                let tx = provider.get_transaction(*tx_hash).await.ok()??;
+
+                if receipt.status != Some(U64::one()) {
+                    return None;
+                }
+
+                let adapter_swaps: Vec<H160> = receipt
+                    .logs
+                    .iter()
+                    .filter(|log| log.topics.first() == Some(&swap_topic))
+                    .map(|log| log.address)
+                    .collect();
+
+                if adapter_swaps != vec![adapter] {
+                    return None;
+                }
+
+                let calldata_gas: u64 = tx
+                    .input
+                    .iter()
+                    .map(|byte| if *byte == 0 { 4 } else { 16 })
+                    .sum();
+
+                Some(AdapterGasSample {
+                    gas_used: receipt.gas_used?,
+                    intrinsic_gas: U256::from(21000 + calldata_gas),
+                })
+            }
+        }))
+        .await;
+
+        Ok(samples.into_iter().flatten().collect())
+    }
+
+    // @dev admin call without signer, it's simulated, sent or exported to Safe batch
+    pub fn build_admin_tx(
+        call: &AdapterAdminCall,
+        adapter: H160,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let adapter_contract = YakAdapter::new(adapter, provider);
+
+        match call {
+            AdapterAdminCall::SetSwapGasEstimate(estimate) => {
+                adapter_contract.set_swap_gas_estimate(*estimate).tx
+            }
+            AdapterAdminCall::SetAllowances => adapter_contract.set_allowances().tx,
+            AdapterAdminCall::RevokeAllowance(token, spender) => {
+                adapter_contract.revoke_allowance(*token, *spender).tx
+            }
+            AdapterAdminCall::RecoverERC20(token, amount) => {
+                adapter_contract.recover_erc20(*token, *amount).tx
+            }
+            AdapterAdminCall::RecoverAVAX(amount) => adapter_contract.recover_avax(*amount).tx,
+            AdapterAdminCall::TransferOwnership(owner) => {
+                adapter_contract.transfer_ownership(*owner).tx
+            }
+            AdapterAdminCall::RenounceOwnership => adapter_contract.renounce_ownership().tx,
+        }
+    }
+}
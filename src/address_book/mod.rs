@@ -0,0 +1,253 @@
+use std::{fmt, fs, path::Path};
+
+use ethers::{types::H160, utils::to_checksum};
+use serde::{Deserialize, Serialize};
+
+use crate::db::DB;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookEntry {
+    pub label: String,
+    pub address: H160,
+    // @dev None means the address is the same on every chain
+    pub chain_id: Option<u32>,
+}
+
+impl fmt::Display for AddressBookEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.chain_id {
+            Some(chain_id) => write!(
+                f,
+                "{} \t {} (chain id: {})",
+                self.label,
+                to_checksum(&self.address, None),
+                chain_id
+            ),
+            None => write!(f, "{} \t {}", self.label, to_checksum(&self.address, None)),
+        }
+    }
+}
+
+pub enum AddressBookError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Csv(usize),
+}
+
+impl From<std::io::Error> for AddressBookError {
+    fn from(err: std::io::Error) -> AddressBookError {
+        AddressBookError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AddressBookError {
+    fn from(err: serde_json::Error) -> AddressBookError {
+        AddressBookError::Json(err)
+    }
+}
+
+impl fmt::Display for AddressBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressBookError::Io(err) => write!(f, "file error: {}", err),
+            AddressBookError::Json(err) => write!(f, "wrong json: {}", err),
+            AddressBookError::Csv(line) => write!(f, "wrong csv on line {}", line),
+        }
+    }
+}
+
+pub struct AddressBook {}
+
+impl AddressBook {
+    const DB_ADDRESS_BOOK_LIST: &'static str = "address-book";
+    const CSV_HEADER: &'static str = "label,address,chain_id";
+
+    pub fn get_entries() -> Vec<AddressBookEntry> {
+        let db_instance = DB.lock().unwrap();
+
+        let mut entries: Vec<AddressBookEntry> = Vec::new();
+
+        if !db_instance.lexists(Self::DB_ADDRESS_BOOK_LIST) {
+            return entries;
+        }
+
+        for entry in db_instance.liter(Self::DB_ADDRESS_BOOK_LIST) {
+            entries.push(entry.get_item::<AddressBookEntry>().unwrap());
+        }
+
+        entries
+    }
+
+    pub fn get_entries_for_chain(chain_id: u32) -> Vec<AddressBookEntry> {
+        Self::get_entries()
+            .into_iter()
+            .filter(|entry| entry.chain_id.is_none() || entry.chain_id == Some(chain_id))
+            .collect()
+    }
+
+    // @dev returns false if the same address is already stored for the chain
+    pub fn save_entry(entry: AddressBookEntry) -> bool {
+        let exists = Self::get_entries()
+            .iter()
+            .any(|stored| stored.address == entry.address && stored.chain_id == entry.chain_id);
+
+        if exists {
+            return false;
+        }
+
+        let mut db_instance = DB.lock().unwrap();
+        if !db_instance.lexists(Self::DB_ADDRESS_BOOK_LIST) {
+            db_instance.lcreate(Self::DB_ADDRESS_BOOK_LIST).unwrap();
+        }
+
+        db_instance.ladd(Self::DB_ADDRESS_BOOK_LIST, &entry);
+
+        true
+    }
+
+    pub fn remove_entry(entry: AddressBookEntry) {
+        let mut db_instance = DB.lock().unwrap();
+
+        let entries_len = db_instance.llen(Self::DB_ADDRESS_BOOK_LIST);
+
+        if entries_len <= 1 {
+            db_instance.lrem_list(Self::DB_ADDRESS_BOOK_LIST).unwrap();
+            return;
+        }
+
+        db_instance
+            .lrem_value(Self::DB_ADDRESS_BOOK_LIST, &entry)
+            .unwrap();
+    }
+
+    pub fn get_label(address: H160, chain_id: u32) -> Option<String> {
+        let entries = Self::get_entries_for_chain(chain_id);
+
+        // @dev chain specific label wins over a label for every chain
+        entries
+            .iter()
+            .find(|entry| entry.address == address && entry.chain_id == Some(chain_id))
+            .or_else(|| entries.iter().find(|entry| entry.address == address))
+            .map(|entry| entry.label.to_owned())
+    }
+
+    // @dev "label (0x..)" for known addresses, checksummed address otherwise
+    pub fn format_address(address: H160, chain_id: u32) -> String {
+        match Self::get_label(address, chain_id) {
+            Some(label) => format!("{} ({})", label, to_checksum(&address, None)),
+            None => to_checksum(&address, None),
+        }
+    }
+
+    // @dev format is chosen by file extension, json otherwise
+    pub fn export(path: &str) -> Result<usize, AddressBookError> {
+        let entries = Self::get_entries();
+
+        let content = if Self::is_csv(path) {
+            let mut lines = vec![Self::CSV_HEADER.to_owned()];
+
+            for entry in &entries {
+                lines.push(format!(
+                    "{},{},{}",
+                    Self::escape_csv(&entry.label),
+                    to_checksum(&entry.address, None),
+                    entry
+                        .chain_id
+                        .map(|chain_id| chain_id.to_string())
+                        .unwrap_or_default()
+                ));
+            }
+
+            lines.join("\n") + "\n"
+        } else {
+            serde_json::to_string_pretty(&entries)?
+        };
+
+        fs::write(path, content)?;
+
+        Ok(entries.len())
+    }
+
+    // @dev returns number of new entries, already stored entries are skipped
+    pub fn import(path: &str) -> Result<usize, AddressBookError> {
+        let content = fs::read_to_string(path)?;
+
+        let entries = if Self::is_csv(path) {
+            Self::parse_csv(&content)?
+        } else {
+            serde_json::from_str::<Vec<AddressBookEntry>>(&content)?
+        };
+
+        let imported = entries
+            .into_iter()
+            .filter(|entry| Self::save_entry(entry.to_owned()))
+            .count();
+
+        Ok(imported)
+    }
+
+    fn is_csv(path: &str) -> bool {
+        Path::new(path)
+            .extension()
+            .map(|extension| extension.eq_ignore_ascii_case("csv"))
+            .unwrap_or(false)
+    }
+
+    fn escape_csv(value: &str) -> String {
+        if value.contains(',') || value.contains('"') {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_owned()
+        }
+    }
+
+    // @dev label may be quoted, address and chain id never contain commas
+    fn parse_csv(content: &str) -> Result<Vec<AddressBookEntry>, AddressBookError> {
+        let mut entries = vec![];
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || (index == 0 && line == Self::CSV_HEADER) {
+                continue;
+            }
+
+            let mut fields: Vec<&str> = line.rsplitn(3, ',').collect();
+            fields.reverse();
+
+            if fields.len() != 3 {
+                return Err(AddressBookError::Csv(index + 1));
+            }
+
+            let label = fields[0].trim();
+            let label = if label.len() >= 2 && label.starts_with('"') && label.ends_with('"') {
+                label[1..label.len() - 1].replace("\"\"", "\"")
+            } else {
+                label.to_owned()
+            };
+
+            let address = fields[1]
+                .trim()
+                .parse::<H160>()
+                .map_err(|_| AddressBookError::Csv(index + 1))?;
+
+            let chain_id = match fields[2].trim() {
+                "" => None,
+                chain_id => Some(
+                    chain_id
+                        .parse::<u32>()
+                        .map_err(|_| AddressBookError::Csv(index + 1))?,
+                ),
+            };
+
+            entries.push(AddressBookEntry {
+                label,
+                address,
+                chain_id,
+            });
+        }
+
+        Ok(entries)
+    }
+}
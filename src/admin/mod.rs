@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use ethers::{
+    prelude::{k256::ecdsa::SigningKey, SignerMiddleware},
+    providers::{Http, Middleware, Provider, ProviderError},
+    signers::Wallet,
+    types::{transaction::eip2718::TypedTransaction, TransactionReceipt, H160, U256},
+};
+
+use crate::network::Network;
+
+// @dev owner-only calls of router and adapters, always simulated before sending
+pub struct Admin {}
+
+impl Admin {
+    // @dev eth_call and gas estimate from owner, revert reason is in the error
+    #[tokio::main]
+    pub async fn simulate(
+        mut tx: TypedTransaction,
+        from: H160,
+        current_network: Arc<Network>,
+    ) -> Result<U256, ProviderError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        tx.set_from(from);
+
+        provider.call(&tx, None).await?;
+
+        provider.estimate_gas(&tx, None).await
+    }
+
+    #[tokio::main]
+    pub async fn send(
+        tx: TypedTransaction,
+        signer: &Wallet<SigningKey>,
+        current_network: Arc<Network>,
+    ) -> Option<TransactionReceipt> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let provider = SignerMiddleware::new_with_provider_chain(provider, signer.to_owned())
+            .await
+            .unwrap();
+
+        let pending_tx = provider
+            .send_transaction(tx, None)
+            .await
+            .expect("Error when admin call");
+
+        pending_tx
+            .await
+            .expect("Error while getting confirmations on admin call")
+    }
+}
@@ -0,0 +1,138 @@
+use std::{collections::HashSet, sync::Arc};
+
+use ethers::{
+    providers::{Http, Middleware, Provider, ProviderError},
+    types::{Filter, H160, H256, U256},
+    utils::keccak256,
+};
+use futures::future;
+
+use crate::{abis::ERC20, network::Network, token::Token};
+
+#[derive(Clone)]
+pub struct AllowanceEntry {
+    pub token: H160,
+    pub symbol: String,
+    pub decimals: u32,
+    pub spender: H160,
+    pub amount: U256,
+}
+
+impl AllowanceEntry {
+    // @dev approvals of U256::MAX are decreased on transferFrom by some tokens
+    pub fn is_unlimited(&self) -> bool {
+        self.amount >= U256::MAX >> 1
+    }
+}
+
+pub struct Allowance {}
+
+impl Allowance {
+    // @dev public RPCs limit block range of eth_getLogs
+    const LOGS_BLOCK_RANGE: u64 = 2048;
+
+    // @dev (token, spender) pairs from Approval events of last `blocks` and every known token
+    // @dev with every known spender, only non-zero current allowances are returned
+    #[tokio::main]
+    pub async fn scan(
+        owner: H160,
+        blocks: u64,
+        known_spenders: Vec<H160>,
+        known_tokens: Vec<Token>,
+        current_network: Arc<Network>,
+    ) -> Result<Vec<AllowanceEntry>, ProviderError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let latest_block = provider.get_block_number().await?.as_u64();
+
+        let approval_topic = H256::from(keccak256("Approval(address,address,uint256)"));
+
+        let mut pairs: HashSet<(H160, H160)> = HashSet::new();
+
+        let mut from_block = latest_block.saturating_sub(blocks);
+
+        while from_block <= latest_block {
+            let to_block = (from_block + Self::LOGS_BLOCK_RANGE - 1).min(latest_block);
+
+            let filter = Filter::new()
+                .from_block(from_block)
+                .to_block(to_block)
+                .topic0(approval_topic)
+                .topic1(H256::from(owner));
+
+            for log in provider.get_logs(&filter).await? {
+                // @dev ERC721 Approval has the same signature but indexed token id
+                if log.topics.len() == 3 {
+                    pairs.insert((log.address, H160::from(log.topics[2])));
+                }
+            }
+
+            from_block = to_block + 1;
+        }
+
+        for token in &known_tokens {
+            if let Ok(token_address) = token.address.parse::<H160>() {
+                if Token::is_native(token_address) {
+                    continue;
+                }
+
+                for spender in &known_spenders {
+                    pairs.insert((token_address, *spender));
+                }
+            }
+        }
+
+        let pairs: Vec<(H160, H160)> = pairs.into_iter().collect();
+
+        let allowances = future::join_all(pairs.iter().map(|(token, spender)| {
+            let token_contract = ERC20::new(*token, provider.clone());
+
+            async move { token_contract.allowance(owner, *spender).call().await }
+        }))
+        .await;
+
+        let mut entries: Vec<AllowanceEntry> = vec![];
+
+        for ((token, spender), allowance) in pairs.into_iter().zip(allowances) {
+            let amount = match allowance {
+                Ok(amount) if !amount.is_zero() => amount,
+                _ => continue,
+            };
+
+            let known_token = known_tokens
+                .iter()
+                .find(|known| known.address.parse::<H160>().ok() == Some(token));
+
+            let (symbol, decimals) = match known_token {
+                Some(known_token) => (known_token.symbol.to_owned(), known_token.decimals),
+                None => {
+                    let token_contract = ERC20::new(token, provider.clone());
+
+                    (
+                        token_contract
+                            .symbol()
+                            .call()
+                            .await
+                            .unwrap_or_else(|_| "UNKNOWN".to_owned()),
+                        token_contract.decimals().call().await.unwrap_or(18) as u32,
+                    )
+                }
+            };
+
+            entries.push(AllowanceEntry {
+                token,
+                symbol,
+                decimals,
+                spender,
+                amount,
+            });
+        }
+
+        entries.sort_by(|a, b| a.symbol.cmp(&b.symbol).then(a.spender.cmp(&b.spender)));
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethers::{
+    abi,
+    providers::{call_raw::spoof, Http, Middleware, Provider, ProviderError},
+    types::{transaction::eip2718::TypedTransaction, Bytes, H160, H256, U256},
+    utils::keccak256,
+};
+use futures::future;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abis::{YakAdapter, YakRouter, ERC20},
+    db::DB,
+    network::Network,
+    query::adapters::Adapter,
+    token::Token,
+};
+
+pub struct Benchmark {}
+
+// @dev single adapter swap from wrapped native, gas is eth_estimateGas of adapter swap
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResult {
+    pub adapter: H160,
+    pub name: String,
+    pub token_out: H160,
+    pub token_out_symbol: String,
+    pub amount_in: U256,
+    pub gas_used: U256,
+    pub intrinsic_gas: U256,
+    pub swap_gas_estimate: U256,
+}
+
+impl BenchmarkResult {
+    pub fn measured_gas(&self) -> U256 {
+        self.gas_used.saturating_sub(self.intrinsic_gas)
+    }
+
+    // @dev measured gas against swapGasEstimate in percent, positive when estimate is too low
+    pub fn estimate_diff(&self) -> f64 {
+        if self.swap_gas_estimate.is_zero() {
+            return 0.0;
+        }
+
+        let measured_gas = self.measured_gas();
+
+        let (diff, sign) = if measured_gas >= self.swap_gas_estimate {
+            (measured_gas - self.swap_gas_estimate, 1.0)
+        } else {
+            (self.swap_gas_estimate - measured_gas, -1.0)
+        };
+
+        // @dev per mille in U256, only the result is converted
+        let per_mille = diff.saturating_mul(U256::from(1000)) / self.swap_gas_estimate;
+
+        match u64::try_from(per_mille) {
+            Ok(per_mille) => sign * per_mille as f64 / 10.0,
+            Err(_) => sign * f64::INFINITY,
+        }
+    }
+
+    pub fn is_same_case(&self, other: &BenchmarkResult) -> bool {
+        self.adapter == other.adapter
+            && self.token_out == other.token_out
+            && self.amount_in == other.amount_in
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkRun {
+    pub timestamp: u64,
+    pub block_number: u64,
+    pub results: Vec<BenchmarkResult>,
+    // @dev cases that could not be simulated, not stored
+    #[serde(skip)]
+    pub failures: Vec<String>,
+}
+
+impl Benchmark {
+    pub const DB_BENCHMARKS: &'static str = "benchmarks";
+    // @dev older runs are dropped
+    const MAX_RUNS: usize = 50;
+    // @dev balanceOf mapping slot of WETH9 style wrapped native (name, symbol, decimals, balanceOf)
+    const WRAPPED_BALANCE_SLOT: u64 = 3;
+
+    // @dev pairs are wrapped native to every router trusted token. Adapter wrapped native balance
+    // @dev is overridden, so RPC has to support state override in eth_call and eth_estimateGas
+    #[tokio::main]
+    pub async fn run(
+        adapters: Vec<Adapter>,
+        amounts: Vec<U256>,
+        current_network: Arc<Network>,
+    ) -> Result<BenchmarkRun, ProviderError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let wrapped_native = Token::load_native_wrapped(&current_network).await?;
+
+        let yak_router_address = current_network
+            .yak_router
+            .as_ref()
+            .and_then(|yak_router| yak_router.parse::<H160>().ok())
+            .ok_or_else(|| ProviderError::CustomError("No Yak Router address".to_owned()))?;
+
+        let router_contract = YakRouter::new(yak_router_address, provider.clone());
+
+        let block_number = provider.get_block_number().await?.as_u64();
+
+        let trusted_tokens_count = router_contract
+            .trusted_tokens_count()
+            .call()
+            .await
+            .map_err(|err| ProviderError::CustomError(err.to_string()))?;
+
+        let mut tokens_out: Vec<(H160, String)> = vec![];
+
+        for i in 0..trusted_tokens_count.as_u32() {
+            let token = router_contract
+                .trusted_tokens(U256::from(i))
+                .call()
+                .await
+                .map_err(|err| ProviderError::CustomError(err.to_string()))?;
+
+            if token == wrapped_native {
+                continue;
+            }
+
+            let symbol = ERC20::new(token, provider.clone())
+                .symbol()
+                .call()
+                .await
+                .unwrap_or_else(|_| "UNKNOWN".to_owned());
+
+            tokens_out.push((token, symbol));
+        }
+
+        // @dev output goes to the router, like in a routed swap, no wallet is needed
+        let to = yak_router_address;
+
+        Self::check_balance_override(&provider, wrapped_native, to).await?;
+
+        let mut results: Vec<BenchmarkResult> = vec![];
+        let mut failures: Vec<String> = vec![];
+
+        for adapter in &adapters {
+            let adapter_contract = YakAdapter::new(adapter.address, provider.clone());
+
+            let swap_gas_estimate = adapter_contract
+                .swap_gas_estimate()
+                .call()
+                .await
+                .unwrap_or_default();
+
+            let cases: Vec<(&(H160, String), U256)> = tokens_out
+                .iter()
+                .flat_map(|token_out| amounts.iter().map(move |amount| (token_out, *amount)))
+                .collect();
+
+            let adapter_results = future::join_all(cases.into_iter().map(
+                |((token_out, token_out_symbol), amount_in)| {
+                    let adapter_contract = &adapter_contract;
+                    let provider = &provider;
+
+                    async move {
+                        let amount_out = adapter_contract
+                            .query(amount_in, wrapped_native, *token_out)
+                            .call()
+                            .await
+                            .map_err(|err| {
+                                format!("{} {}: {}", adapter.name, token_out_symbol, err)
+                            })?;
+
+                        // @dev no liquidity for the pair, nothing to measure
+                        if amount_out.is_zero() {
+                            return Ok(None);
+                        }
+
+                        // @dev router transfers amount in to the adapter before swap
+                        let mut state = spoof::state();
+                        state.account(wrapped_native).store(
+                            Self::wrapped_balance_slot(adapter.address),
+                            Self::to_storage_value(amount_in),
+                        );
+
+                        let tx: TypedTransaction = adapter_contract
+                            .swap(
+                                amount_in,
+                                amount_out * 99 / 100,
+                                wrapped_native,
+                                *token_out,
+                                to,
+                            )
+                            .tx;
+
+                        let gas_used = provider
+                            .request::<_, U256>("eth_estimateGas", (&tx, "latest", &state))
+                            .await
+                            .map_err(|err| {
+                                format!("{} {}: {}", adapter.name, token_out_symbol, err)
+                            })?;
+
+                        let calldata_gas: u64 = tx
+                            .data()
+                            .map(|data| {
+                                data.iter()
+                                    .map(|byte| if *byte == 0 { 4 } else { 16 })
+                                    .sum()
+                            })
+                            .unwrap_or_default();
+
+                        Ok::<Option<BenchmarkResult>, String>(Some(BenchmarkResult {
+                            adapter: adapter.address,
+                            name: adapter.name.to_owned(),
+                            token_out: *token_out,
+                            token_out_symbol: token_out_symbol.to_owned(),
+                            amount_in,
+                            gas_used,
+                            intrinsic_gas: U256::from(21000 + calldata_gas),
+                            swap_gas_estimate,
+                        }))
+                    }
+                },
+            ))
+            .await;
+
+            for adapter_result in adapter_results {
+                match adapter_result {
+                    Ok(result) => results.extend(result),
+                    Err(failure) => failures.push(failure),
+                }
+            }
+        }
+
+        Ok(BenchmarkRun {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            block_number,
+            results,
+            failures,
+        })
+    }
+
+    fn wrapped_balance_slot(holder: H160) -> H256 {
+        H256::from(keccak256(abi::encode(&[
+            abi::Token::Address(holder),
+            abi::Token::Uint(U256::from(Self::WRAPPED_BALANCE_SLOT)),
+        ])))
+    }
+
+    fn to_storage_value(value: U256) -> H256 {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+
+        H256::from(bytes)
+    }
+
+    // @dev fails when RPC ignores state override or wrapped native has another storage layout
+    async fn check_balance_override(
+        provider: &Arc<Provider<Http>>,
+        wrapped_native: H160,
+        holder: H160,
+    ) -> Result<(), ProviderError> {
+        let amount = U256::exp10(18);
+
+        let mut state = spoof::state();
+        state.account(wrapped_native).store(
+            Self::wrapped_balance_slot(holder),
+            Self::to_storage_value(amount),
+        );
+
+        let tx: TypedTransaction = ERC20::new(wrapped_native, provider.clone())
+            .balance_of(holder)
+            .tx;
+
+        let balance = provider
+            .request::<_, Bytes>("eth_call", (&tx, "latest", &state))
+            .await
+            .map_err(|err| {
+                ProviderError::CustomError(format!("RPC doesn't support state override: {}", err))
+            })?;
+
+        if U256::from_big_endian(&balance) != amount {
+            return Err(ProviderError::CustomError(
+                "Wrapped native balance can't be overridden, benchmark needs a WETH9 style wrapped native"
+                    .to_owned(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn save_run(chain_id: u32, run: &BenchmarkRun) {
+        let mut db_instance = DB.lock().unwrap();
+
+        let mut benchmarks = db_instance
+            .get::<HashMap<u32, Vec<BenchmarkRun>>>(Self::DB_BENCHMARKS)
+            .unwrap_or_default();
+
+        let runs = benchmarks.entry(chain_id).or_default();
+
+        runs.push(run.to_owned());
+
+        if runs.len() > Self::MAX_RUNS {
+            runs.drain(..runs.len() - Self::MAX_RUNS);
+        }
+
+        db_instance.set(Self::DB_BENCHMARKS, &benchmarks).unwrap();
+    }
+
+    // @dev oldest first
+    pub fn get_runs(chain_id: u32) -> Vec<BenchmarkRun> {
+        let db_instance = DB.lock().unwrap();
+
+        db_instance
+            .get::<HashMap<u32, Vec<BenchmarkRun>>>(Self::DB_BENCHMARKS)
+            .and_then(|benchmarks| benchmarks.get(&chain_id).cloned())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(gas_used: u64, swap_gas_estimate: u64) -> BenchmarkResult {
+        BenchmarkResult {
+            adapter: H160::zero(),
+            name: "Adapter".to_owned(),
+            token_out: H160::zero(),
+            token_out_symbol: "TKN".to_owned(),
+            amount_in: U256::one(),
+            gas_used: U256::from(gas_used),
+            intrinsic_gas: U256::from(21000),
+            swap_gas_estimate: U256::from(swap_gas_estimate),
+        }
+    }
+
+    #[test]
+    fn estimate_diff_is_signed_percent() {
+        assert_eq!(result(141000, 100000).estimate_diff(), 20.0);
+        assert_eq!(result(101000, 100000).estimate_diff(), -20.0);
+        assert_eq!(result(121000, 100000).estimate_diff(), 0.0);
+        assert_eq!(result(121000, 0).estimate_diff(), 0.0);
+    }
+
+    #[test]
+    fn estimate_diff_does_not_overflow() {
+        let mut result = result(0, 1);
+        result.gas_used = U256::MAX;
+
+        assert_eq!(result.estimate_diff(), f64::INFINITY);
+    }
+}
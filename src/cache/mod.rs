@@ -0,0 +1,80 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{db::CACHE_DB, settings::Settings};
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    updated_at: u64,
+    value: T,
+}
+
+pub struct Cache {}
+
+impl Cache {
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    // @dev TTL 0 is "No cache", entries are never fresh but still written for offline mode
+    fn is_disabled() -> bool {
+        Settings::get_cache_ttl() == 0
+    }
+
+    // @dev None when missing or older than cache TTL setting
+    pub fn get<T: DeserializeOwned>(key: &str) -> Option<T> {
+        if Self::is_disabled() {
+            return None;
+        }
+
+        let ttl = Settings::get_cache_ttl() as u64 * 60;
+
+        let cache_db = CACHE_DB.lock().unwrap();
+
+        let entry = cache_db.get::<CacheEntry<T>>(key)?;
+
+        if Self::now().saturating_sub(entry.updated_at) > ttl {
+            return None;
+        }
+
+        Some(entry.value)
+    }
+
+    // @dev ignores TTL, used in offline mode or when fetching failed
+    pub fn get_stale<T: DeserializeOwned>(key: &str) -> Option<T> {
+        let cache_db = CACHE_DB.lock().unwrap();
+
+        cache_db.get::<CacheEntry<T>>(key).map(|entry| entry.value)
+    }
+
+    pub fn set<T: Serialize>(key: &str, value: &T) {
+        let mut cache_db = CACHE_DB.lock().unwrap();
+
+        cache_db
+            .set(
+                key,
+                &CacheEntry {
+                    updated_at: Self::now(),
+                    value,
+                },
+            )
+            .unwrap();
+    }
+
+    // @dev manual refresh, everything is fetched again on next use
+    pub fn clear() -> usize {
+        let mut cache_db = CACHE_DB.lock().unwrap();
+
+        let keys = cache_db.get_all();
+
+        for key in &keys {
+            cache_db.rem(key).unwrap();
+        }
+
+        keys.len()
+    }
+}
@@ -3,10 +3,13 @@ use std::sync::Mutex;
 use lazy_static::lazy_static;
 use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
 
-const DB_PATH: &'static str = "./cli.db";
+const DB_PATH: &str = "./cli.db";
+// @dev big fetched lists live apart, AutoDump rewrites the whole file on every set
+const CACHE_DB_PATH: &str = "./cache.db";
 
 lazy_static! {
     pub static ref DB: Mutex<PickleDb> = Mutex::new(init_db(DB_PATH));
+    pub static ref CACHE_DB: Mutex<PickleDb> = Mutex::new(init_db(CACHE_DB_PATH));
 }
 
 fn init_db(path: &str) -> PickleDb {
@@ -21,3 +24,23 @@ fn init_db(path: &str) -> PickleDb {
         ),
     }
 }
+
+// @dev other sessions write the same file, loaded state is kept when the file can't be read
+pub fn reload_db(db_instance: &mut PickleDb) {
+    let db = PickleDb::load(
+        DB_PATH,
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Bin,
+    )
+    .or_else(|_| {
+        PickleDb::load(
+            DB_PATH,
+            PickleDbDumpPolicy::AutoDump,
+            SerializationMethod::Json,
+        )
+    });
+
+    if let Ok(db) = db {
+        *db_instance = db;
+    }
+}
@@ -0,0 +1,499 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use ethers::types::{transaction::eip2718::TypedTransaction, H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abis::{FormattedOfferWithGas, Trade},
+    db::{reload_db, DB},
+    network::Network,
+    order::LimitOrders,
+    swap::Swap,
+    token::Token,
+};
+
+pub struct Dca {}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum DcaStatus {
+    Active,
+    Completed,
+    Cancelled,
+}
+
+impl fmt::Display for DcaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DcaStatus::Active => write!(f, "active"),
+            DcaStatus::Completed => write!(f, "completed"),
+            DcaStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum DcaRunStatus {
+    Filled,
+    // @dev price impact or gas above plan limits, nothing sent
+    Skipped,
+    Failed,
+}
+
+impl fmt::Display for DcaRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DcaRunStatus::Filled => write!(f, "filled"),
+            DcaRunStatus::Skipped => write!(f, "skipped"),
+            DcaRunStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaRun {
+    pub timestamp: u64,
+    pub status: DcaRunStatus,
+    pub amount_out: Option<U256>,
+    // @dev in slippage format, e.g. 5 = 0.5%
+    pub price_impact: Option<u32>,
+    pub gas_cost: Option<U256>,
+    pub tx_hash: Option<H256>,
+    pub reason: Option<String>,
+}
+
+// @dev only filled runs are counted, plan ends on whichever limit comes first
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaSchedule {
+    pub interval: u64,
+    pub max_runs: Option<u32>,
+    pub end_at: Option<u64>,
+}
+
+// @dev max slippage is both price impact limit and slippage of the swap, e.g. 5 = 0.5%
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaLimits {
+    pub max_slippage: u32,
+    pub max_gas_cost: U256,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DcaPlan {
+    pub id: u32,
+    pub account: H160,
+    pub token_in: Token,
+    pub token_out: Token,
+    pub amount_per_run: U256,
+    pub schedule: DcaSchedule,
+    pub limits: DcaLimits,
+    pub next_run_at: u64,
+    pub created_at: u64,
+    pub status: DcaStatus,
+    pub runs: Vec<DcaRun>,
+}
+
+impl DcaPlan {
+    pub fn filled_runs(&self) -> u32 {
+        self.runs
+            .iter()
+            .filter(|run| run.status == DcaRunStatus::Filled)
+            .count() as u32
+    }
+
+    pub fn is_finished(&self, now: u64) -> bool {
+        self.schedule
+            .max_runs
+            .map(|max_runs| self.filled_runs() >= max_runs)
+            .unwrap_or(false)
+            || self
+                .schedule
+                .end_at
+                .map(|end_at| now >= end_at)
+                .unwrap_or(false)
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.status == DcaStatus::Active && now >= self.next_run_at
+    }
+
+    // @dev missed intervals are not caught up, next run is the first slot after now
+    pub fn schedule_next_run(&mut self, now: u64) {
+        while self.next_run_at <= now {
+            self.next_run_at += self.schedule.interval;
+        }
+    }
+
+    // @dev runs left until end date or run limit, None when the plan has neither
+    pub fn remaining_runs(&self, now: u64) -> Option<u32> {
+        let by_count = self
+            .schedule
+            .max_runs
+            .map(|max_runs| max_runs.saturating_sub(self.filled_runs()));
+
+        let by_date = self.schedule.end_at.map(|end_at| {
+            (end_at.saturating_sub(now.max(self.next_run_at)) / self.schedule.interval) as u32 + 1
+        });
+
+        match (by_count, by_date) {
+            (Some(by_count), Some(by_date)) => Some(by_count.min(by_date)),
+            (by_count, by_date) => by_count.or(by_date),
+        }
+    }
+
+    // @dev swapNoSplit of one run with plan slippage instead of slippage setting
+    pub fn build_run_tx(
+        &self,
+        offer: FormattedOfferWithGas,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let mut trade = Trade {
+            amount_in: self.amount_per_run,
+            amount_out: *offer.amounts.last().unwrap(),
+            path: offer.path,
+            adapters: offer.adapters,
+        };
+
+        trade.handle_slippage(self.limits.max_slippage);
+
+        let from_to_native = Swap::decide_from_to_native(
+            self.token_in.address.parse::<H160>().unwrap(),
+            self.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        let mut tx = Swap::build_trade_tx(trade, self.account, from_to_native, current_network);
+
+        tx.set_from(self.account);
+
+        tx
+    }
+}
+
+impl Dca {
+    pub const DB_DCA_PLANS: &'static str = "dca-plans";
+
+    // @dev output for a small reference amount is the price without impact
+    pub const REFERENCE_DIVISOR: u64 = 100;
+
+    // @dev run output against reference rate, in slippage format
+    pub fn price_impact(
+        amount_in: U256,
+        amount_out: U256,
+        reference_in: U256,
+        reference_out: U256,
+    ) -> u32 {
+        if reference_in.is_zero() || reference_out.is_zero() {
+            return 1000;
+        }
+
+        // @dev rates compared cross multiplied, amounts too large to multiply are not priced
+        let (amount_out, expected_out) = match (
+            amount_out.checked_mul(reference_in),
+            reference_out.checked_mul(amount_in),
+        ) {
+            (Some(amount_out), Some(expected_out)) => (amount_out, expected_out),
+            _ => return 1000,
+        };
+
+        if expected_out <= amount_out {
+            return 0;
+        }
+
+        let difference = expected_out - amount_out;
+
+        let impact = match difference.checked_mul(U256::from(1000)) {
+            Some(difference) => difference / expected_out,
+            None => difference / (expected_out / 1000),
+        };
+
+        impact.as_u32().min(1000)
+    }
+
+    pub fn get_plans(chain_id: u32) -> Vec<DcaPlan> {
+        let db_instance = DB.lock().unwrap();
+
+        db_instance
+            .get::<HashMap<u32, Vec<DcaPlan>>>(Self::DB_DCA_PLANS)
+            .and_then(|plans| plans.get(&chain_id).cloned())
+            .unwrap_or_default()
+    }
+
+    pub fn create(
+        chain_id: u32,
+        account: H160,
+        token_in: Token,
+        token_out: Token,
+        amount_per_run: U256,
+        schedule: DcaSchedule,
+        limits: DcaLimits,
+    ) -> DcaPlan {
+        let id = Self::get_plans(chain_id)
+            .iter()
+            .map(|plan| plan.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        let now = LimitOrders::now();
+
+        let plan = DcaPlan {
+            id,
+            account,
+            token_in,
+            token_out,
+            amount_per_run,
+            schedule,
+            limits,
+            next_run_at: now,
+            created_at: now,
+            status: DcaStatus::Active,
+            runs: vec![],
+        };
+
+        Self::save_plan(chain_id, &plan);
+
+        plan
+    }
+
+    // @dev plan with the same id is replaced
+    pub fn save_plan(chain_id: u32, plan: &DcaPlan) {
+        let mut db_instance = DB.lock().unwrap();
+
+        let mut plans = db_instance
+            .get::<HashMap<u32, Vec<DcaPlan>>>(Self::DB_DCA_PLANS)
+            .unwrap_or_default();
+
+        let chain_plans = plans.entry(chain_id).or_default();
+
+        match chain_plans.iter_mut().find(|saved| saved.id == plan.id) {
+            Some(saved) => *saved = plan.to_owned(),
+            None => chain_plans.push(plan.to_owned()),
+        }
+
+        db_instance.set(Self::DB_DCA_PLANS, &plans).unwrap();
+    }
+
+    // @dev read from disk, so changes from another session are seen
+    pub fn get_plan(chain_id: u32, id: u32) -> Option<DcaPlan> {
+        reload_db(&mut DB.lock().unwrap());
+
+        Self::get_plans(chain_id)
+            .into_iter()
+            .find(|plan| plan.id == id)
+    }
+
+    // @dev scheduler changes are written only while the stored plan is still active
+    pub fn save_active_plan(chain_id: u32, plan: &DcaPlan) -> bool {
+        let mut db_instance = DB.lock().unwrap();
+
+        reload_db(&mut db_instance);
+
+        let mut plans = db_instance
+            .get::<HashMap<u32, Vec<DcaPlan>>>(Self::DB_DCA_PLANS)
+            .unwrap_or_default();
+
+        let saved = plans
+            .entry(chain_id)
+            .or_default()
+            .iter_mut()
+            .find(|saved| saved.id == plan.id && saved.status == DcaStatus::Active);
+
+        match saved {
+            Some(saved) => *saved = plan.to_owned(),
+            None => return false,
+        }
+
+        db_instance.set(Self::DB_DCA_PLANS, &plans).unwrap();
+
+        true
+    }
+
+    pub fn cancel(chain_id: u32, id: u32) -> Result<DcaPlan, &'static str> {
+        let mut plan = Self::get_plan(chain_id, id).ok_or("Plan not found")?;
+
+        if plan.status != DcaStatus::Active {
+            return Err("Only active plans can be cancelled");
+        }
+
+        plan.status = DcaStatus::Cancelled;
+
+        Self::save_plan(chain_id, &plan);
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: &str, symbol: &str) -> Token {
+        Token {
+            address: address.to_owned(),
+            chain_id: Some(43114),
+            decimals: 18,
+            name: symbol.to_owned(),
+            symbol: symbol.to_owned(),
+            is_trusted: true,
+        }
+    }
+
+    fn run(status: DcaRunStatus) -> DcaRun {
+        DcaRun {
+            timestamp: 0,
+            status,
+            amount_out: None,
+            price_impact: None,
+            gas_cost: None,
+            tx_hash: None,
+            reason: None,
+        }
+    }
+
+    fn plan(max_runs: Option<u32>, end_at: Option<u64>) -> DcaPlan {
+        DcaPlan {
+            id: 1,
+            account: H160::zero(),
+            token_in: token("0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7", "WAVAX"),
+            token_out: token("0xB97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E", "USDC"),
+            amount_per_run: U256::from(1000),
+            schedule: DcaSchedule {
+                interval: 100,
+                max_runs,
+                end_at,
+            },
+            limits: DcaLimits {
+                max_slippage: 5,
+                max_gas_cost: U256::zero(),
+            },
+            next_run_at: 1000,
+            created_at: 1000,
+            status: DcaStatus::Active,
+            runs: vec![],
+        }
+    }
+
+    #[test]
+    fn remaining_runs_without_limits_is_none() {
+        assert_eq!(plan(None, None).remaining_runs(900), None);
+    }
+
+    #[test]
+    fn remaining_runs_counts_only_filled_runs() {
+        let mut plan = plan(Some(5), None);
+
+        plan.runs = vec![
+            run(DcaRunStatus::Filled),
+            run(DcaRunStatus::Skipped),
+            run(DcaRunStatus::Failed),
+            run(DcaRunStatus::Filled),
+        ];
+
+        assert_eq!(plan.remaining_runs(900), Some(3));
+    }
+
+    #[test]
+    fn remaining_runs_by_end_date() {
+        // @dev slots at 1000, 1100, 1200 and 1300
+        assert_eq!(plan(None, Some(1350)).remaining_runs(900), Some(4));
+        // @dev overdue run is due now, then 1200 and 1300
+        assert_eq!(plan(None, Some(1350)).remaining_runs(1150), Some(3));
+    }
+
+    #[test]
+    fn remaining_runs_takes_lower_limit() {
+        assert_eq!(plan(Some(2), Some(1350)).remaining_runs(900), Some(2));
+        assert_eq!(plan(Some(10), Some(1350)).remaining_runs(900), Some(4));
+    }
+
+    #[test]
+    fn schedule_next_run_moves_to_next_slot() {
+        let mut plan = plan(None, None);
+
+        plan.schedule_next_run(1000);
+
+        assert_eq!(plan.next_run_at, 1100);
+    }
+
+    #[test]
+    fn schedule_next_run_skips_missed_slots() {
+        let mut plan = plan(None, None);
+
+        plan.schedule_next_run(1250);
+
+        assert_eq!(plan.next_run_at, 1300);
+    }
+
+    #[test]
+    fn price_impact_at_reference_rate_is_zero() {
+        let impact = Dca::price_impact(
+            U256::from(10000),
+            U256::from(100000),
+            U256::from(100),
+            U256::from(1000),
+        );
+
+        assert_eq!(impact, 0);
+    }
+
+    #[test]
+    fn price_impact_above_reference_rate_is_zero() {
+        let impact = Dca::price_impact(
+            U256::from(10000),
+            U256::from(101000),
+            U256::from(100),
+            U256::from(1000),
+        );
+
+        assert_eq!(impact, 0);
+    }
+
+    #[test]
+    fn price_impact_in_slippage_format() {
+        // @dev 1% less than reference rate
+        let impact = Dca::price_impact(
+            U256::from(10000),
+            U256::from(99000),
+            U256::from(100),
+            U256::from(1000),
+        );
+
+        assert_eq!(impact, 10);
+    }
+
+    #[test]
+    fn price_impact_without_reference_is_max() {
+        let impact = Dca::price_impact(
+            U256::from(10000),
+            U256::from(99000),
+            U256::zero(),
+            U256::zero(),
+        );
+
+        assert_eq!(impact, 1000);
+    }
+
+    #[test]
+    fn price_impact_of_large_amounts() {
+        let amount = U256::MAX / 4;
+
+        // @dev half of reference rate, difference too large to multiply by 1000
+        let impact = Dca::price_impact(amount, amount, U256::from(1), U256::from(2));
+
+        assert_eq!(impact, 500);
+    }
+
+    #[test]
+    fn price_impact_overflow_is_max() {
+        let impact = Dca::price_impact(
+            U256::from(10000),
+            U256::MAX,
+            U256::from(2),
+            U256::from(1000),
+        );
+
+        assert_eq!(impact, 1000);
+    }
+}
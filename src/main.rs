@@ -1,9 +1,24 @@
-use terminal::Terminal;
+use terminal::{
+    benchmark::BenchmarkScreen, dca::DcaScreen, order::LimitOrderScreen, query::QueryScreen,
+    router::RouterScreen, Terminal,
+};
 
 mod abis;
+mod adapter;
+mod address_book;
+mod admin;
+mod allowance;
+mod benchmark;
+mod cache;
 mod db;
+mod dca;
 mod network;
+mod offline;
+mod order;
+mod permit;
 mod query;
+mod router;
+mod safe;
 mod settings;
 mod swap;
 mod terminal;
@@ -11,5 +26,15 @@ mod token;
 mod wallet;
 
 fn main() {
-    Terminal::render_on_launch();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // @dev subcommands run once and exit, interactive menu otherwise
+    match args.first().map(String::as_str) {
+        Some("router-info") => RouterScreen::command(&args[1..]),
+        Some("quote") => QueryScreen::command(&args[1..]),
+        Some("limit-orders") => LimitOrderScreen::command(&args[1..]),
+        Some("dca") => DcaScreen::command(&args[1..]),
+        Some("benchmark") => BenchmarkScreen::command(&args[1..]),
+        _ => Terminal::render_on_launch(),
+    }
 }
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fmt};
 
 use crate::db::DB;
+use pickledb::PickleDb;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -15,6 +16,9 @@ pub struct Network {
     pub currency_decimals: u8,
     pub is_testnet: bool,
     pub yak_router: Option<String>,
+    // @dev WETH-like contract, router WAVAX() is used when None or not valid
+    #[serde(default)]
+    pub wrapped_native: Option<String>,
 }
 
 impl std::fmt::Display for Network {
@@ -31,6 +35,7 @@ impl Network {
     pub const DB_CURRENT_NETWORK: &'static str = "current-network";
     pub const DB_CUSTOM_RPC: &'static str = "current-rpc";
     pub const DB_CUSTOM_YAK_ROUTER: &'static str = "current-yak";
+    pub const DB_CUSTOM_WRAPPED_NATIVE: &'static str = "current-wrapped-native";
 
     pub fn get_current_network() -> Self {
         let mut db_instance = DB.try_lock().unwrap();
@@ -38,24 +43,7 @@ impl Network {
         let current_network = db_instance.get::<Self>(Self::DB_CURRENT_NETWORK);
 
         if let Some(mut current_network) = current_network {
-            let custom_rpcs = db_instance.get::<HashMap<u32, String>>(Self::DB_CUSTOM_RPC);
-            let custom_yaks = db_instance.get::<HashMap<u32, String>>(Self::DB_CUSTOM_YAK_ROUTER);
-
-            if let Some(custom_rpcs) = custom_rpcs {
-                let custom_rpc = custom_rpcs.get(&current_network.chain_id);
-
-                if let Some(custom_rpc) = custom_rpc {
-                    current_network.set_custom_rpc(custom_rpc.to_owned());
-                }
-            }
-
-            if let Some(custom_yaks) = custom_yaks {
-                let custom_yak_router = custom_yaks.get(&current_network.chain_id);
-
-                if let Some(custom_yak_router) = custom_yak_router {
-                    current_network.set_custom_router(custom_yak_router.to_owned())
-                }
-            }
+            current_network.apply_custom_settings(&db_instance);
 
             current_network
         } else {
@@ -69,6 +57,57 @@ impl Network {
         }
     }
 
+    // @dev any supported network with custom RPC and router applied, not only the current one
+    pub fn get_network(chain_id: u32) -> Option<Self> {
+        let db_instance = DB.try_lock().unwrap();
+
+        let mut network = Self::get_supported_networks()
+            .into_iter()
+            .find(|network| network.chain_id == chain_id)?;
+
+        network.apply_custom_settings(&db_instance);
+
+        Some(network)
+    }
+
+    fn apply_custom_settings(&mut self, db_instance: &PickleDb) {
+        let custom_rpcs = db_instance.get::<HashMap<u32, String>>(Self::DB_CUSTOM_RPC);
+        let custom_yaks = db_instance.get::<HashMap<u32, String>>(Self::DB_CUSTOM_YAK_ROUTER);
+
+        if let Some(custom_rpcs) = custom_rpcs {
+            let custom_rpc = custom_rpcs.get(&self.chain_id);
+
+            if let Some(custom_rpc) = custom_rpc {
+                self.set_custom_rpc(custom_rpc.to_owned());
+            }
+        }
+
+        if let Some(custom_yaks) = custom_yaks {
+            let custom_yak_router = custom_yaks.get(&self.chain_id);
+
+            if let Some(custom_yak_router) = custom_yak_router {
+                self.set_custom_router(custom_yak_router.to_owned())
+            }
+        }
+
+        // @dev current network stored before wrapped native was configurable has no address
+        if self.wrapped_native.is_none() {
+            self.wrapped_native = Self::get_supported_networks()
+                .into_iter()
+                .find(|network| network.chain_id == self.chain_id)
+                .and_then(|network| network.wrapped_native);
+        }
+
+        let custom_wrapped_natives =
+            db_instance.get::<HashMap<u32, String>>(Self::DB_CUSTOM_WRAPPED_NATIVE);
+
+        if let Some(custom_wrapped_natives) = custom_wrapped_natives {
+            if let Some(custom_wrapped_native) = custom_wrapped_natives.get(&self.chain_id) {
+                self.wrapped_native = Some(custom_wrapped_native.to_owned());
+            }
+        }
+    }
+
     pub fn set_current_network(network: Network) {
         let mut db_instance = DB.try_lock().unwrap();
         db_instance.set(Self::DB_CURRENT_NETWORK, &network).unwrap();
@@ -114,6 +153,20 @@ impl Network {
         }
     }
 
+    pub fn update_wrapped_native(chain_id: u32, new_wrapped_native: String) {
+        let mut db_instance = DB.try_lock().unwrap();
+
+        let mut custom_wrapped_natives = db_instance
+            .get::<HashMap<u32, String>>(Self::DB_CUSTOM_WRAPPED_NATIVE)
+            .unwrap_or_default();
+
+        custom_wrapped_natives.insert(chain_id, new_wrapped_native);
+
+        db_instance
+            .set(Self::DB_CUSTOM_WRAPPED_NATIVE, &custom_wrapped_natives)
+            .unwrap();
+    }
+
     pub fn get_supported_networks() -> [Self; 10] {
         [
             // Avalanche
@@ -128,6 +181,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: false,
                 yak_router: Some("0xC4729E56b831d74bBc18797e0e17A295fA77488c".to_owned()),
+                wrapped_native: Some("0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7".to_owned()),
             },
             Network {
                 chain_id: 43113,
@@ -140,6 +194,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: true,
                 yak_router: None,
+                wrapped_native: Some("0xd00ae08403B9bbb9124bB305C09058E32C39A48c".to_owned()),
             },
             // Dogechain
             Network {
@@ -153,6 +208,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: false,
                 yak_router: Some("0x985d014DA6e6C781ec3FF77E8Fd48c30174F3d96".to_owned()),
+                wrapped_native: Some("0xB7ddC6414bf4F5515b52D8BdD69973Ae205ff101".to_owned()),
             },
             Network {
                 chain_id: 568,
@@ -165,6 +221,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: true,
                 yak_router: None,
+                wrapped_native: None,
             },
             // Optimism
             Network {
@@ -178,6 +235,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: false,
                 yak_router: None,
+                wrapped_native: Some("0x4200000000000000000000000000000000000006".to_owned()),
             },
             Network {
                 chain_id: 69,
@@ -190,6 +248,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: true,
                 yak_router: None,
+                wrapped_native: Some("0x4200000000000000000000000000000000000006".to_owned()),
             },
             // Arbitrum
             Network {
@@ -203,6 +262,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: false,
                 yak_router: None,
+                wrapped_native: Some("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".to_owned()),
             },
             Network {
                 chain_id: 421613,
@@ -215,6 +275,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: true,
                 yak_router: None,
+                wrapped_native: Some("0xEe01c0CD76354C383B8c7B4e65EA88D00B06f36f".to_owned()),
             },
             // Aurora
             Network {
@@ -228,6 +289,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: false,
                 yak_router: None,
+                wrapped_native: Some("0xC9BdeEd33CD01541e1eeD10f90519d2C06Fe3feB".to_owned()),
             },
             Network {
                 chain_id: 1313161555,
@@ -240,6 +302,7 @@ impl Network {
                 currency_decimals: 18,
                 is_testnet: true,
                 yak_router: None,
+                wrapped_native: None,
             },
         ]
     }
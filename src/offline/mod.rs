@@ -0,0 +1,268 @@
+use std::{fs, io, path::Path, sync::Arc};
+
+use ethers::{
+    prelude::k256::ecdsa::SigningKey,
+    providers::{Http, Middleware, Provider, ProviderError},
+    signers::{Signer, Wallet},
+    types::{
+        transaction::eip2718::{TypedTransaction, TypedTransactionError},
+        BlockNumber, Bytes, Eip1559TransactionRequest, TransactionReceipt, TransactionRequest,
+        H160, H256, U256,
+    },
+    utils::rlp::Rlp,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::network::Network;
+
+pub struct Offline {}
+
+pub enum OfflineError {
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    ProviderError(ProviderError),
+    DecodeError(TypedTransactionError),
+    WrongSigner(H160),
+    // @dev chain id of the signed transaction and of the current network
+    WrongChain(Option<u64>, u32),
+}
+
+impl From<std::io::Error> for OfflineError {
+    fn from(err: std::io::Error) -> OfflineError {
+        OfflineError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for OfflineError {
+    fn from(err: serde_json::Error) -> OfflineError {
+        OfflineError::JsonError(err)
+    }
+}
+
+impl From<ProviderError> for OfflineError {
+    fn from(err: ProviderError) -> OfflineError {
+        OfflineError::ProviderError(err)
+    }
+}
+
+impl From<TypedTransactionError> for OfflineError {
+    fn from(err: TypedTransactionError) -> OfflineError {
+        OfflineError::DecodeError(err)
+    }
+}
+
+impl std::fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OfflineError::IoError(err) => write!(f, "file error: {}", err),
+            OfflineError::JsonError(err) => write!(f, "wrong transaction file: {}", err),
+            OfflineError::ProviderError(err) => write!(f, "provider error: {}", err),
+            OfflineError::DecodeError(err) => write!(f, "wrong raw transaction: {}", err),
+            OfflineError::WrongSigner(from) => {
+                write!(f, "transaction must be signed by {:?}", from)
+            }
+            OfflineError::WrongChain(Some(chain_id), current_chain_id) => write!(
+                f,
+                "transaction is signed for chain id {}, current network chain id is {}",
+                chain_id, current_chain_id
+            ),
+            OfflineError::WrongChain(None, _) => {
+                write!(
+                    f,
+                    "transaction has no chain id and can be replayed on any chain"
+                )
+            }
+        }
+    }
+}
+
+// @dev fully populated transaction, everything needed to sign it without network access
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub from: H160,
+    pub to: H160,
+    pub data: Bytes,
+    pub value: U256,
+    pub nonce: U256,
+    pub gas: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub chain_id: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedTransaction {
+    pub hash: H256,
+    pub raw_transaction: Bytes,
+    pub chain_id: u64,
+}
+
+impl UnsignedTransaction {
+    pub fn to_typed_transaction(&self) -> TypedTransaction {
+        if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
+            (self.max_fee_per_gas, self.max_priority_fee_per_gas)
+        {
+            let tx = Eip1559TransactionRequest::new()
+                .from(self.from)
+                .to(self.to)
+                .data(self.data.to_owned())
+                .value(self.value)
+                .nonce(self.nonce)
+                .gas(self.gas)
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .chain_id(self.chain_id);
+
+            return TypedTransaction::Eip1559(tx);
+        }
+
+        let mut tx = TransactionRequest::new()
+            .from(self.from)
+            .to(self.to)
+            .data(self.data.to_owned())
+            .value(self.value)
+            .nonce(self.nonce)
+            .gas(self.gas)
+            .chain_id(self.chain_id);
+
+        tx.gas_price = self.gas_price;
+
+        TypedTransaction::Legacy(tx)
+    }
+}
+
+impl Offline {
+    // @dev fills nonce, gas and fees from the current network for the given sender
+    #[tokio::main]
+    pub async fn populate(
+        mut tx: TypedTransaction,
+        from: H160,
+        current_network: Arc<Network>,
+    ) -> Result<UnsignedTransaction, OfflineError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        tx.set_from(from);
+        tx.set_chain_id(current_network.chain_id);
+
+        let nonce = provider
+            .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+            .await?;
+        tx.set_nonce(nonce);
+
+        provider.fill_transaction(&mut tx, None).await?;
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) = match &tx {
+            TypedTransaction::Eip1559(inner) => {
+                (inner.max_fee_per_gas, inner.max_priority_fee_per_gas)
+            }
+            _ => (None, None),
+        };
+
+        Ok(UnsignedTransaction {
+            from,
+            to: *tx.to().and_then(|to| to.as_address()).unwrap(),
+            data: tx.data().cloned().unwrap_or_default(),
+            value: tx.value().cloned().unwrap_or_default(),
+            nonce,
+            gas: *tx.gas().unwrap(),
+            gas_price: if max_fee_per_gas.is_none() {
+                tx.gas_price()
+            } else {
+                None
+            },
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            chain_id: current_network.chain_id as u64,
+        })
+    }
+
+    pub fn export_unsigned(tx: &UnsignedTransaction, path: &str) -> Result<(), OfflineError> {
+        let json = serde_json::to_string_pretty(tx)?;
+
+        fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    pub fn load_unsigned(path: &str) -> Result<UnsignedTransaction, OfflineError> {
+        let json = fs::read_to_string(path)?;
+
+        Ok(serde_json::from_str::<UnsignedTransaction>(&json)?)
+    }
+
+    // @dev no network access here, the file already has everything needed
+    pub fn sign(
+        tx: &UnsignedTransaction,
+        signer: &Wallet<SigningKey>,
+    ) -> Result<SignedTransaction, OfflineError> {
+        if signer.address() != tx.from {
+            return Err(OfflineError::WrongSigner(tx.from));
+        }
+
+        let typed_tx = tx.to_typed_transaction();
+        let signature = signer.sign_transaction_sync(&typed_tx);
+
+        Ok(SignedTransaction {
+            hash: typed_tx.hash(&signature),
+            raw_transaction: typed_tx.rlp_signed(&signature),
+            chain_id: tx.chain_id,
+        })
+    }
+
+    pub fn export_signed(tx: &SignedTransaction, path: &str) -> Result<(), OfflineError> {
+        let json = serde_json::to_string_pretty(tx)?;
+
+        fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    // @dev accepts either a signed transaction file or a raw 0x-prefixed hex string, existing file wins
+    pub fn load_signed(path_or_raw: &str) -> Result<Bytes, OfflineError> {
+        if Path::new(path_or_raw).is_file() {
+            let json = fs::read_to_string(path_or_raw)?;
+
+            return Ok(serde_json::from_str::<SignedTransaction>(&json)?.raw_transaction);
+        }
+
+        path_or_raw.parse::<Bytes>().map_err(|_| {
+            OfflineError::IoError(io::Error::new(
+                io::ErrorKind::NotFound,
+                "neither a file nor a raw transaction hex",
+            ))
+        })
+    }
+
+    // @dev raw transaction is decoded first, one signed for another chain is not sent
+    #[tokio::main]
+    pub async fn broadcast(
+        raw_transaction: Bytes,
+        current_network: Arc<Network>,
+    ) -> Result<Option<TransactionReceipt>, OfflineError> {
+        let (tx, _) = TypedTransaction::decode_signed(&Rlp::new(&raw_transaction))?;
+
+        let chain_id = tx.chain_id().map(|chain_id| chain_id.as_u64());
+
+        if chain_id != Some(current_network.chain_id as u64) {
+            return Err(OfflineError::WrongChain(chain_id, current_network.chain_id));
+        }
+
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let pending_tx = provider.send_raw_transaction(raw_transaction).await?;
+
+        Ok(pending_tx.await?)
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethers::types::{transaction::eip2718::TypedTransaction, H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abis::{FormattedOfferWithGas, Trade},
+    db::{reload_db, DB},
+    network::Network,
+    swap::Swap,
+    token::Token,
+};
+
+pub struct LimitOrders {}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+    Expired,
+    // @dev fill tx reverted on chain, order is not retried to not burn gas
+    Failed,
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderStatus::Open => write!(f, "open"),
+            OrderStatus::Filled => write!(f, "filled"),
+            OrderStatus::Cancelled => write!(f, "cancelled"),
+            OrderStatus::Expired => write!(f, "expired"),
+            OrderStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitOrder {
+    pub id: u32,
+    pub account: H160,
+    pub token_in: Token,
+    pub token_out: Token,
+    pub amount_in: U256,
+    pub min_amount_out: U256,
+    pub expiry: u64,
+    pub created_at: u64,
+    pub status: OrderStatus,
+    // @dev last watcher check, kept for status tracking
+    pub last_amount_out: Option<U256>,
+    pub last_checked: Option<u64>,
+    pub last_error: Option<String>,
+    pub tx_hash: Option<H256>,
+}
+
+impl LimitOrder {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expiry
+    }
+
+    // @dev swapNoSplit with order floor as amountOut, no slippage setting applied
+    pub fn build_fill_tx(
+        &self,
+        offer: FormattedOfferWithGas,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let trade = Trade {
+            amount_in: self.amount_in,
+            amount_out: self.min_amount_out,
+            path: offer.path,
+            adapters: offer.adapters,
+        };
+
+        let from_to_native = Swap::decide_from_to_native(
+            self.token_in.address.parse::<H160>().unwrap(),
+            self.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        let mut tx = Swap::build_trade_tx(trade, self.account, from_to_native, current_network);
+
+        tx.set_from(self.account);
+
+        tx
+    }
+}
+
+impl LimitOrders {
+    pub const DB_LIMIT_ORDERS: &'static str = "limit-orders";
+
+    pub fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    pub fn get_orders(chain_id: u32) -> Vec<LimitOrder> {
+        let db_instance = DB.lock().unwrap();
+
+        db_instance
+            .get::<HashMap<u32, Vec<LimitOrder>>>(Self::DB_LIMIT_ORDERS)
+            .and_then(|orders| orders.get(&chain_id).cloned())
+            .unwrap_or_default()
+    }
+
+    pub fn create(
+        chain_id: u32,
+        account: H160,
+        token_in: Token,
+        token_out: Token,
+        amount_in: U256,
+        min_amount_out: U256,
+        expiry: u64,
+    ) -> LimitOrder {
+        let id = Self::get_orders(chain_id)
+            .iter()
+            .map(|order| order.id)
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        let order = LimitOrder {
+            id,
+            account,
+            token_in,
+            token_out,
+            amount_in,
+            min_amount_out,
+            expiry,
+            created_at: Self::now(),
+            status: OrderStatus::Open,
+            last_amount_out: None,
+            last_checked: None,
+            last_error: None,
+            tx_hash: None,
+        };
+
+        Self::save_order(chain_id, &order);
+
+        order
+    }
+
+    // @dev order with the same id is replaced
+    pub fn save_order(chain_id: u32, order: &LimitOrder) {
+        let mut db_instance = DB.lock().unwrap();
+
+        let mut orders = db_instance
+            .get::<HashMap<u32, Vec<LimitOrder>>>(Self::DB_LIMIT_ORDERS)
+            .unwrap_or_default();
+
+        let chain_orders = orders.entry(chain_id).or_default();
+
+        match chain_orders.iter_mut().find(|saved| saved.id == order.id) {
+            Some(saved) => *saved = order.to_owned(),
+            None => chain_orders.push(order.to_owned()),
+        }
+
+        db_instance.set(Self::DB_LIMIT_ORDERS, &orders).unwrap();
+    }
+
+    // @dev read from disk, so changes from another session are seen
+    pub fn get_order(chain_id: u32, id: u32) -> Option<LimitOrder> {
+        reload_db(&mut DB.lock().unwrap());
+
+        Self::get_orders(chain_id)
+            .into_iter()
+            .find(|order| order.id == id)
+    }
+
+    // @dev watcher save, skipped when order was cancelled or closed by another session meanwhile
+    pub fn save_open_order(chain_id: u32, order: &LimitOrder) -> bool {
+        let mut db_instance = DB.lock().unwrap();
+
+        reload_db(&mut db_instance);
+
+        let mut orders = db_instance
+            .get::<HashMap<u32, Vec<LimitOrder>>>(Self::DB_LIMIT_ORDERS)
+            .unwrap_or_default();
+
+        let saved = orders
+            .entry(chain_id)
+            .or_default()
+            .iter_mut()
+            .find(|saved| saved.id == order.id && saved.status == OrderStatus::Open);
+
+        match saved {
+            Some(saved) => *saved = order.to_owned(),
+            None => return false,
+        }
+
+        db_instance.set(Self::DB_LIMIT_ORDERS, &orders).unwrap();
+
+        true
+    }
+
+    pub fn cancel(chain_id: u32, id: u32) -> Result<LimitOrder, &'static str> {
+        let mut order = Self::get_order(chain_id, id).ok_or("Order not found")?;
+
+        if order.status != OrderStatus::Open {
+            return Err("Only open orders can be cancelled");
+        }
+
+        order.status = OrderStatus::Cancelled;
+
+        Self::save_order(chain_id, &order);
+
+        Ok(order)
+    }
+}
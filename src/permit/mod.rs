@@ -0,0 +1,406 @@
+use std::{fmt, sync::Arc};
+
+use ethers::{
+    abi::{self, ParamType},
+    prelude::{k256::ecdsa::SigningKey, SignerMiddleware},
+    providers::{Http, Middleware, Provider, ProviderError},
+    signers::{Signer, Wallet},
+    types::{
+        BlockNumber, Bytes, Signature, TransactionReceipt, TransactionRequest, H160, H256, U256,
+    },
+    utils::{id, keccak256},
+};
+
+use crate::{network::Network, settings::Settings, token::Token};
+
+// @dev EIP-712 domain, fields are None when token doesn't use them
+#[derive(Clone)]
+pub struct PermitDomain {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<U256>,
+    pub verifying_contract: Option<H160>,
+    pub salt: Option<[u8; 32]>,
+}
+
+impl fmt::Display for PermitDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (version: {})",
+            self.name.to_owned().unwrap_or_default(),
+            self.version.to_owned().unwrap_or_else(|| "none".to_owned())
+        )
+    }
+}
+
+impl PermitDomain {
+    pub fn separator(&self) -> [u8; 32] {
+        let mut fields: Vec<&str> = vec![];
+        let mut values: Vec<abi::Token> = vec![];
+
+        if let Some(name) = &self.name {
+            fields.push("string name");
+            values.push(abi::Token::FixedBytes(keccak256(name).to_vec()));
+        }
+
+        if let Some(version) = &self.version {
+            fields.push("string version");
+            values.push(abi::Token::FixedBytes(keccak256(version).to_vec()));
+        }
+
+        if let Some(chain_id) = self.chain_id {
+            fields.push("uint256 chainId");
+            values.push(abi::Token::Uint(chain_id));
+        }
+
+        if let Some(verifying_contract) = self.verifying_contract {
+            fields.push("address verifyingContract");
+            values.push(abi::Token::Address(verifying_contract));
+        }
+
+        if let Some(salt) = self.salt {
+            fields.push("bytes32 salt");
+            values.push(abi::Token::FixedBytes(salt.to_vec()));
+        }
+
+        let domain_typehash = keccak256(format!("EIP712Domain({})", fields.join(",")));
+
+        values.insert(0, abi::Token::FixedBytes(domain_typehash.to_vec()));
+
+        keccak256(abi::encode(&values))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PermitKind {
+    // @dev permit(owner, spender, value, deadline, v, r, s), supported by router
+    Eip2612,
+    // @dev permit(holder, spender, nonce, expiry, allowed, v, r, s), DAI and forks
+    Dai,
+}
+
+#[derive(Clone)]
+pub struct TokenPermit {
+    pub kind: PermitKind,
+    pub domain: PermitDomain,
+}
+
+pub struct Permit {}
+
+impl Permit {
+    const EIP2612_TYPE: &'static str =
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+    const EIP2612_SIGNATURE: &'static str =
+        "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)";
+    const DAI_TYPE: &'static str =
+        "Permit(address holder,address spender,uint256 nonce,uint256 expiry,bool allowed)";
+    const DAI_SIGNATURE: &'static str =
+        "permit(address,address,uint256,uint256,bool,uint8,bytes32,bytes32)";
+
+    // @dev domain is only returned when it hashes to token DOMAIN_SEPARATOR(), otherwise
+    // @dev signature would be rejected on-chain and approve should be used instead
+    #[tokio::main]
+    pub async fn get_permit(
+        token_address: H160,
+        owner: H160,
+        current_network: Arc<Network>,
+    ) -> Option<TokenPermit> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let code = provider.get_code(token_address, None).await.ok()?;
+
+        if code.is_empty() {
+            return None;
+        }
+
+        let domain_separator =
+            Self::call(&provider, token_address, "DOMAIN_SEPARATOR()", &[]).await?;
+
+        if domain_separator.len() != 32 {
+            return None;
+        }
+
+        Self::call(
+            &provider,
+            token_address,
+            "nonces(address)",
+            &[abi::Token::Address(owner)],
+        )
+        .await?;
+
+        // @dev proxies (e.g. USDC) don't have selectors in their own code, PERMIT_TYPEHASH() is checked then
+        let kind = if Self::has_selector(&code, Self::EIP2612_SIGNATURE) {
+            PermitKind::Eip2612
+        } else if Self::has_selector(&code, Self::DAI_SIGNATURE) {
+            PermitKind::Dai
+        } else {
+            match Self::call(&provider, token_address, "PERMIT_TYPEHASH()", &[]).await {
+                Some(typehash) if typehash.as_ref() == keccak256(Self::EIP2612_TYPE) => {
+                    PermitKind::Eip2612
+                }
+                Some(typehash) if typehash.as_ref() == keccak256(Self::DAI_TYPE) => PermitKind::Dai,
+                Some(_) => return None,
+                None => PermitKind::Eip2612,
+            }
+        };
+
+        let mut candidates: Vec<PermitDomain> = vec![];
+
+        // @dev EIP-5267 describes the domain exactly
+        if let Some(output) = Self::call(&provider, token_address, "eip712Domain()", &[]).await {
+            if let Some(domain) = Self::decode_eip712_domain(&output) {
+                candidates.push(domain);
+            }
+        }
+
+        let name = Self::call(&provider, token_address, "name()", &[])
+            .await
+            .and_then(|output| Token::decode_string_or_bytes32(Ok(output)))
+            .map(|(name, _)| name);
+
+        let version = Self::call(&provider, token_address, "version()", &[])
+            .await
+            .and_then(|output| Token::decode_string_or_bytes32(Ok(output)))
+            .map(|(version, _)| version);
+
+        if let Some(name) = name {
+            // @dev tokens without version() mostly use "1", some (e.g. UNI) have no version in domain
+            let mut versions = vec![version, Some("1".to_owned()), Some("2".to_owned()), None];
+            versions.dedup();
+
+            for version in versions {
+                candidates.push(PermitDomain {
+                    name: Some(name.to_owned()),
+                    version,
+                    chain_id: Some(U256::from(current_network.chain_id)),
+                    verifying_contract: Some(token_address),
+                    salt: None,
+                });
+            }
+        }
+
+        let domain = candidates
+            .into_iter()
+            .find(|domain| domain.separator() == domain_separator.as_ref())?;
+
+        Some(TokenPermit { kind, domain })
+    }
+
+    pub fn eip2612_struct_hash(
+        owner: H160,
+        spender: H160,
+        value: U256,
+        nonce: U256,
+        deadline: U256,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            abi::Token::FixedBytes(keccak256(Self::EIP2612_TYPE).to_vec()),
+            abi::Token::Address(owner),
+            abi::Token::Address(spender),
+            abi::Token::Uint(value),
+            abi::Token::Uint(nonce),
+            abi::Token::Uint(deadline),
+        ]))
+    }
+
+    pub fn dai_struct_hash(
+        holder: H160,
+        spender: H160,
+        nonce: U256,
+        expiry: U256,
+        allowed: bool,
+    ) -> [u8; 32] {
+        keccak256(abi::encode(&[
+            abi::Token::FixedBytes(keccak256(Self::DAI_TYPE).to_vec()),
+            abi::Token::Address(holder),
+            abi::Token::Address(spender),
+            abi::Token::Uint(nonce),
+            abi::Token::Uint(expiry),
+            abi::Token::Bool(allowed),
+        ]))
+    }
+
+    pub fn sign(
+        domain: &PermitDomain,
+        struct_hash: [u8; 32],
+        signer: &Wallet<SigningKey>,
+    ) -> Signature {
+        signer.sign_hash(Self::digest(domain, struct_hash))
+    }
+
+    // @dev checked before sending, otherwise wrong signature or passed deadline only shows up as revert
+    pub async fn validate<M: Middleware>(
+        provider: &M,
+        domain: &PermitDomain,
+        struct_hash: [u8; 32],
+        signature: &Signature,
+        owner: H160,
+        deadline: U256,
+    ) -> Result<(), &'static str> {
+        let latest_block = provider
+            .get_block(BlockNumber::Latest)
+            .await
+            .ok()
+            .flatten()
+            .ok_or("latest block not found")?;
+
+        if deadline <= latest_block.timestamp {
+            return Err("permit deadline has passed");
+        }
+
+        signature
+            .verify(Self::digest(domain, struct_hash), owner)
+            .map_err(|_| "permit signature doesn't match owner")
+    }
+
+    // @dev deadline is relative to latest block timestamp, not local clock
+    #[tokio::main]
+    pub async fn get_deadline(current_network: Arc<Network>) -> Result<U256, ProviderError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let latest_block = provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| ProviderError::CustomError("Latest block not found".to_owned()))?;
+
+        Ok(latest_block.timestamp + U256::from(Settings::get_permit_deadline()) * U256::from(60))
+    }
+
+    // @dev router only takes EIP-2612 permit, DAI permit is a separate transaction.
+    // @dev it can only set allowance to unlimited or 0, so it's revoked after swap
+    #[tokio::main]
+    pub async fn send_dai_permit(
+        token_address: H160,
+        domain: &PermitDomain,
+        spender: H160,
+        expiry: U256,
+        allowed: bool,
+        signer: &Wallet<SigningKey>,
+        current_network: Arc<Network>,
+    ) -> Option<TransactionReceipt> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let holder = signer.address();
+
+        let nonce = Self::call(
+            &provider,
+            token_address,
+            "nonces(address)",
+            &[abi::Token::Address(holder)],
+        )
+        .await
+        .map(|output| U256::from_big_endian(&output[..32]))
+        .expect("Expect to get nonce");
+
+        let struct_hash = Self::dai_struct_hash(holder, spender, nonce, expiry, allowed);
+
+        let signature = Self::sign(domain, struct_hash, signer);
+
+        if let Err(err) =
+            Self::validate(&provider, domain, struct_hash, &signature, holder, expiry).await
+        {
+            println!("Permit is not sent: {}", err);
+            return None;
+        }
+
+        let calldata = [
+            id(Self::DAI_SIGNATURE).as_ref(),
+            &abi::encode(&[
+                abi::Token::Address(holder),
+                abi::Token::Address(spender),
+                abi::Token::Uint(nonce),
+                abi::Token::Uint(expiry),
+                abi::Token::Bool(allowed),
+                abi::Token::Uint(U256::from(signature.v)),
+                abi::Token::Uint(signature.r),
+                abi::Token::Uint(signature.s),
+            ]),
+        ]
+        .concat();
+
+        let provider = SignerMiddleware::new_with_provider_chain(provider, signer.to_owned())
+            .await
+            .unwrap();
+
+        let permit_tx = TransactionRequest::new()
+            .to(token_address)
+            .data(Bytes::from(calldata));
+
+        let pending_tx = provider
+            .send_transaction(permit_tx, None)
+            .await
+            .expect("Error when permit call");
+
+        pending_tx
+            .await
+            .expect("Error while getting confirmations on permit")
+    }
+
+    fn digest(domain: &PermitDomain, struct_hash: [u8; 32]) -> H256 {
+        let digest_input = [
+            &[0x19, 0x01],
+            domain.separator().as_ref(),
+            struct_hash.as_ref(),
+        ]
+        .concat();
+
+        H256::from(keccak256(digest_input))
+    }
+
+    fn has_selector(code: &Bytes, signature: &str) -> bool {
+        let selector = id(signature);
+
+        code.windows(4).any(|window| window == selector)
+    }
+
+    async fn call(
+        provider: &Provider<Http>,
+        to: H160,
+        signature: &str,
+        args: &[abi::Token],
+    ) -> Option<Bytes> {
+        let calldata = [id(signature).as_ref(), &abi::encode(args)].concat();
+
+        Token::call_raw(provider, to, Bytes::from(calldata))
+            .await
+            .ok()
+            .filter(|output| !output.is_empty())
+    }
+
+    // @dev (bytes1 fields, string name, string version, uint256 chainId, address verifyingContract,
+    // @dev bytes32 salt, uint256[] extensions), fields is a bitmap of used values
+    fn decode_eip712_domain(output: &Bytes) -> Option<PermitDomain> {
+        let decoded = abi::decode(
+            &[
+                ParamType::FixedBytes(1),
+                ParamType::String,
+                ParamType::String,
+                ParamType::Uint(256),
+                ParamType::Address,
+                ParamType::FixedBytes(32),
+                ParamType::Array(Box::new(ParamType::Uint(256))),
+            ],
+            output,
+        )
+        .ok()?;
+
+        let fields = decoded[0].to_owned().into_fixed_bytes()?[0];
+
+        let mut salt = [0u8; 32];
+        salt.copy_from_slice(&decoded[5].to_owned().into_fixed_bytes()?);
+
+        let is_used = |bit: u8| fields & bit != 0;
+
+        Some(PermitDomain {
+            name: decoded[1].to_owned().into_string().filter(|_| is_used(1)),
+            version: decoded[2].to_owned().into_string().filter(|_| is_used(2)),
+            chain_id: decoded[3].to_owned().into_uint().filter(|_| is_used(4)),
+            verifying_contract: decoded[4].to_owned().into_address().filter(|_| is_used(8)),
+            salt: Some(salt).filter(|_| is_used(16)),
+        })
+    }
+}
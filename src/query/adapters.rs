@@ -1,7 +1,9 @@
 use std::fmt;
 
-use ethers::types::H160;
+use ethers::types::{H160, U256};
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Adapter {
     pub address: H160,
     pub name: String,
@@ -12,3 +14,13 @@ impl fmt::Display for Adapter {
         write!(f, "{} ({})", self.name, self.address)
     }
 }
+
+// @dev single adapter quote, net amount is None when gas cost can't be priced in token out
+#[derive(Clone)]
+pub struct AdapterQuote {
+    pub adapter: Adapter,
+    pub amount_out: U256,
+    pub gas_estimate: U256,
+    pub net_amount_out: Option<U256>,
+    pub is_router_pick: bool,
+}
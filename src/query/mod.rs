@@ -2,15 +2,17 @@ use std::sync::Arc;
 
 use crate::{
     abis::{FormattedOfferWithGas, YakAdapter, YakRouter},
+    cache::Cache,
     network::Network,
+    settings::Settings,
     token::Token,
 };
-use adapters::Adapter;
+use adapters::{Adapter, AdapterQuote};
 use ethers::{
     providers::{Http, Middleware, Provider, ProviderError},
-    types::{H160, U256},
+    types::{H160, I256, U256, U64},
 };
-use futures::future;
+use futures::{future, stream, StreamExt};
 use serde::Deserialize;
 
 pub mod adapters;
@@ -29,6 +31,10 @@ impl From<reqwest::Error> for ExternalQuoteError {
 }
 
 impl Query {
+    const ONEINCH_NATIVE: &'static str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+    // @dev public RPCs rate limit, route exploration can make thousands of queryAdapter calls
+    const MAX_CONCURRENT_QUERIES: usize = 16;
+
     #[tokio::main]
     pub async fn get_adapters() -> Vec<Adapter> {
         let current_network = Network::get_current_network();
@@ -37,9 +43,26 @@ impl Query {
                 .expect("could not instantiate HTTP Provider"),
         );
 
-        // @todo memo get_adapters to refresh only on yak_router_address change
         // we need it to match address <> name for query
         if let Some(yak_router_address) = current_network.yak_router {
+            // @dev adapters are cached per chain and router
+            let cache_key = format!(
+                "adapters:{}:{}",
+                current_network.chain_id,
+                yak_router_address.to_lowercase()
+            );
+
+            if let Some(adapters) = Cache::get::<Vec<Adapter>>(&cache_key) {
+                return adapters;
+            }
+
+            if Settings::is_offline() {
+                return Cache::get_stale::<Vec<Adapter>>(&cache_key).unwrap_or_else(|| {
+                    println!("Offline mode and adapters are not cached");
+                    vec![]
+                });
+            }
+
             let router_contract = Arc::new(YakRouter::new(
                 yak_router_address.parse::<H160>().unwrap(),
                 provider.clone(),
@@ -76,18 +99,24 @@ impl Query {
                         tasks.push(task);
                     }
 
-                    let completed_tasks = future::join_all(tasks)
+                    let completed_tasks: Vec<Adapter> = future::join_all(tasks)
                         .await
                         .into_iter()
                         // @todo may panic so consider to refactor this
                         .map(|adapter| adapter.unwrap())
                         .collect();
 
+                    Cache::set(&cache_key, &completed_tasks);
+
                     completed_tasks
                 }
-                Err(_err) => {
-                    panic!("Error when call router.adaptersCount");
-                }
+                Err(_err) => match Cache::get_stale::<Vec<Adapter>>(&cache_key) {
+                    Some(adapters) => {
+                        println!("Error when call router.adaptersCount, using cached adapters");
+                        adapters
+                    }
+                    None => panic!("Error when call router.adaptersCount"),
+                },
             }
         } else {
             panic!("No Yak Router address");
@@ -103,20 +132,24 @@ impl Query {
     ) -> Result<U256, ethers::contract::ContractError<ethers::providers::Provider<Http>>> {
         let current_network = Network::get_current_network();
         let provider = Arc::new(
-            Provider::<Http>::try_from(current_network.rpc_url)
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
                 .expect("could not instantiate HTTP Provider"),
         );
 
         let mut token_in = token_in;
 
         if Token::is_native(token_in) {
-            token_in = Token::get_native_wrapped(current_network.chain_id);
+            token_in = Token::load_native_wrapped(&current_network)
+                .await
+                .map_err(|err| ethers::contract::ContractError::ProviderError(err.into()))?;
         }
 
         let mut token_out = token_out;
 
         if Token::is_native(token_out) {
-            token_out = Token::get_native_wrapped(current_network.chain_id);
+            token_out = Token::load_native_wrapped(&current_network)
+                .await
+                .map_err(|err| ethers::contract::ContractError::ProviderError(err.into()))?;
         }
 
         let adapter_contract = YakAdapter::new(adapter.to_owned(), provider);
@@ -131,6 +164,546 @@ impl Query {
         query
     }
 
+    // @dev every adapter is queried directly, router queryNoSplit pick is marked.
+    // @dev gas cost is priced in token out with router queryNoSplit of 1 wrapped native
+    #[tokio::main]
+    pub async fn compare_adapters(
+        adapters: Vec<Adapter>,
+        amount: U256,
+        token_in: H160,
+        token_out: H160,
+    ) -> Result<Vec<AdapterQuote>, ProviderError> {
+        let current_network = Network::get_current_network();
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let wrapped_native = Token::load_native_wrapped(&current_network).await?;
+
+        let token_in = if Token::is_native(token_in) {
+            wrapped_native
+        } else {
+            token_in
+        };
+
+        let token_out = if Token::is_native(token_out) {
+            wrapped_native
+        } else {
+            token_out
+        };
+
+        let yak_router_address = match &current_network.yak_router {
+            Some(yak_router_address) => yak_router_address.parse::<H160>().unwrap(),
+            None => panic!("No Yak Router address"),
+        };
+
+        let router_contract = YakRouter::new(yak_router_address, provider.clone());
+
+        let gas_price = provider.get_gas_price().await?;
+
+        let native_rate = Self::get_native_rate(&router_contract, wrapped_native, token_out).await;
+
+        let router_pick = router_contract
+            .query_no_split(amount, token_in, token_out)
+            .call()
+            .await
+            .ok()
+            .map(|query| query.adapter);
+
+        let quotes = future::join_all(adapters.into_iter().map(|adapter| {
+            let adapter_contract = YakAdapter::new(adapter.address, provider.clone());
+
+            async move {
+                let amount_out = adapter_contract
+                    .query(amount, token_in, token_out)
+                    .call()
+                    .await
+                    .unwrap_or_default();
+                let gas_estimate = adapter_contract
+                    .swap_gas_estimate()
+                    .call()
+                    .await
+                    .unwrap_or_default();
+
+                (adapter, amount_out, gas_estimate)
+            }
+        }))
+        .await;
+
+        let mut adapter_quotes: Vec<AdapterQuote> = quotes
+            .into_iter()
+            .filter(|(_, amount_out, _)| !amount_out.is_zero())
+            .map(|(adapter, amount_out, gas_estimate)| AdapterQuote {
+                is_router_pick: router_pick == Some(adapter.address),
+                net_amount_out: native_rate.map(|native_rate| {
+                    amount_out
+                        .saturating_sub(Self::gas_cost_in(gas_price * gas_estimate, native_rate))
+                }),
+                adapter,
+                amount_out,
+                gas_estimate,
+            })
+            .collect();
+
+        adapter_quotes.sort_by(|a, b| {
+            b.net_amount_out
+                .cmp(&a.net_amount_out)
+                .then(b.amount_out.cmp(&a.amount_out))
+        });
+
+        Ok(adapter_quotes)
+    }
+
+    // @dev paths go through router trusted tokens, each hop takes the adapter with best queryAdapter output.
+    // @dev hops are capped at 3, every extra hop multiplies calls by trusted tokens count
+    #[tokio::main]
+    pub async fn explore_routes(
+        amount: U256,
+        token_in: H160,
+        token_out: H160,
+        max_steps: i32,
+        top: usize,
+    ) -> Result<RouteExploration, ethers::contract::ContractError<ethers::providers::Provider<Http>>>
+    {
+        let current_network = Network::get_current_network();
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let wrapped_native = Token::load_native_wrapped(&current_network)
+            .await
+            .map_err(|err| ethers::contract::ContractError::ProviderError(err.into()))?;
+
+        let token_in = if Token::is_native(token_in) {
+            wrapped_native
+        } else {
+            token_in
+        };
+
+        let token_out = if Token::is_native(token_out) {
+            wrapped_native
+        } else {
+            token_out
+        };
+
+        let yak_router_address = match &current_network.yak_router {
+            Some(yak_router_address) => yak_router_address.parse::<H160>().unwrap(),
+            None => panic!("No Yak Router address"),
+        };
+
+        let router_contract = Arc::new(YakRouter::new(yak_router_address, provider.clone()));
+
+        let trusted_tokens_count = router_contract.trusted_tokens_count().call().await?;
+
+        let trusted_tokens: Vec<H160> =
+            future::try_join_all((0..trusted_tokens_count.as_u32()).map(|i| {
+                let router_contract = router_contract.clone();
+
+                async move { router_contract.trusted_tokens(U256::from(i)).call().await }
+            }))
+            .await?;
+
+        let adapters_count = router_contract.adapters_count().call().await?;
+
+        // @dev (adapter, swapGasEstimate) in router order, queryAdapter takes the index
+        let adapters_gas: Vec<(H160, U256)> =
+            future::try_join_all((0..adapters_count.as_u32()).map(|i| {
+                let router_contract = router_contract.clone();
+                let provider = provider.clone();
+
+                async move {
+                    let adapter = router_contract.adapters(U256::from(i)).call().await?;
+                    let gas_estimate = YakAdapter::new(adapter, provider)
+                        .swap_gas_estimate()
+                        .call()
+                        .await
+                        .unwrap_or_default();
+
+                    Ok::<(H160, U256), ethers::contract::ContractError<Provider<Http>>>((
+                        adapter,
+                        gas_estimate,
+                    ))
+                }
+            }))
+            .await?;
+
+        let gas_price = provider
+            .get_gas_price()
+            .await
+            .map_err(ethers::contract::ContractError::ProviderError)?;
+
+        let native_rate = Self::get_native_rate(&router_contract, wrapped_native, token_out).await;
+
+        let mut partials = vec![FormattedOfferWithGas {
+            amounts: vec![amount],
+            adapters: vec![],
+            path: vec![token_in],
+            gas_estimate: U256::zero(),
+        }];
+
+        let mut routes: Vec<FormattedOfferWithGas> = vec![];
+        let mut failed_queries = 0;
+
+        let max_steps = max_steps.clamp(1, 3);
+
+        for step in 0..max_steps {
+            let mut hops: Vec<(FormattedOfferWithGas, H160)> = vec![];
+
+            for partial in &partials {
+                let mut next_tokens = vec![token_out];
+
+                if step + 1 < max_steps {
+                    next_tokens.extend(trusted_tokens.iter().filter(|token| **token != token_out));
+                }
+
+                for next_token in next_tokens {
+                    if !partial.path.contains(&next_token) {
+                        hops.push((partial.to_owned(), next_token));
+                    }
+                }
+            }
+
+            let hop_queries: Vec<(U256, H160, H160)> = hops
+                .iter()
+                .map(|(partial, next_token)| {
+                    (
+                        *partial.amounts.last().unwrap(),
+                        *partial.path.last().unwrap(),
+                        *next_token,
+                    )
+                })
+                .collect();
+
+            let (hop_results, hop_failed_queries) =
+                Self::query_hops(router_contract.clone(), &adapters_gas, &hop_queries).await;
+
+            failed_queries += hop_failed_queries;
+
+            partials = vec![];
+
+            for ((mut partial, next_token), hop_result) in hops.into_iter().zip(hop_results) {
+                if let Some((amount_out, adapter, gas_estimate)) = hop_result {
+                    partial.amounts.push(amount_out);
+                    partial.adapters.push(adapter);
+                    partial.path.push(next_token);
+                    partial.gas_estimate += gas_estimate;
+
+                    if next_token == token_out {
+                        routes.push(partial);
+                    } else {
+                        partials.push(partial);
+                    }
+                }
+            }
+        }
+
+        let mut route_offers: Vec<RouteOffer> = routes
+            .into_iter()
+            .map(|offer| RouteOffer {
+                net_amount_out: native_rate.map(|native_rate| {
+                    offer
+                        .amounts
+                        .last()
+                        .unwrap()
+                        .saturating_sub(Self::gas_cost_in(
+                            gas_price * offer.gas_estimate,
+                            native_rate,
+                        ))
+                }),
+                offer,
+            })
+            .collect();
+
+        route_offers.sort_by(|a, b| {
+            b.net_amount_out
+                .cmp(&a.net_amount_out)
+                .then(b.offer.amounts.last().cmp(&a.offer.amounts.last()))
+        });
+
+        route_offers.truncate(top);
+
+        Ok(RouteExploration {
+            routes: route_offers,
+            failed_queries,
+        })
+    }
+
+    // @dev greedy marginal split: amount is cut into `parts` chunks and every chunk goes to the route
+    // @dev with best marginal output, route gas is counted once when it gets its first chunk.
+    // @dev routes sharing a hop would be quoted against the same pool twice, so only the first is kept
+    #[tokio::main]
+    pub async fn find_split_offer(
+        amount: U256,
+        routes: Vec<RouteOffer>,
+        adapters: Vec<Adapter>,
+        parts: u32,
+    ) -> Result<SplitOffer, ethers::contract::ContractError<ethers::providers::Provider<Http>>>
+    {
+        let current_network = Network::get_current_network();
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let yak_router_address = match &current_network.yak_router {
+            Some(yak_router_address) => yak_router_address.parse::<H160>().unwrap(),
+            None => panic!("No Yak Router address"),
+        };
+
+        let router_contract = YakRouter::new(yak_router_address, provider);
+
+        let mut used_hops: Vec<(H160, H160, H160)> = vec![];
+        let mut distinct_routes: Vec<RouteOffer> = vec![];
+
+        for route in routes {
+            let hops: Vec<(H160, H160, H160)> = route
+                .offer
+                .adapters
+                .iter()
+                .enumerate()
+                .map(|(i, adapter)| (*adapter, route.offer.path[i], route.offer.path[i + 1]))
+                .collect();
+
+            if hops.iter().all(|hop| !used_hops.contains(hop)) {
+                used_hops.extend(hops);
+                distinct_routes.push(route);
+            }
+        }
+
+        let parts = parts.max(1) as usize;
+
+        // @dev gas of the route priced in token out, 0 when it can't be priced
+        let gas_costs: Vec<U256> = distinct_routes
+            .iter()
+            .map(|route| match route.net_amount_out {
+                Some(net_amount_out) => route
+                    .offer
+                    .amounts
+                    .last()
+                    .unwrap()
+                    .saturating_sub(net_amount_out),
+                None => U256::zero(),
+            })
+            .collect();
+
+        // @dev curves[route][k] is output of route for k chunks
+        let mut curves: Vec<Vec<U256>> = vec![];
+
+        for route in &distinct_routes {
+            let quotes = future::join_all((1..=parts).map(|k| {
+                Self::quote_route(
+                    &router_contract,
+                    &route.offer,
+                    &adapters,
+                    amount * U256::from(k) / U256::from(parts),
+                )
+            }))
+            .await;
+
+            let mut curve = vec![U256::zero()];
+            curve.extend(quotes.into_iter().map(|quote| match quote {
+                Some(quote) => *quote.amounts.last().unwrap(),
+                None => U256::zero(),
+            }));
+
+            curves.push(curve);
+        }
+
+        let mut chunks = vec![0; distinct_routes.len()];
+
+        for _ in 0..parts {
+            let best_route = (0..distinct_routes.len()).max_by_key(|&i| {
+                let chunk_output =
+                    I256::from_raw(curves[i][chunks[i] + 1]) - I256::from_raw(curves[i][chunks[i]]);
+
+                if chunks[i] == 0 {
+                    chunk_output - I256::from_raw(gas_costs[i])
+                } else {
+                    chunk_output
+                }
+            });
+
+            match best_route {
+                Some(best_route) => chunks[best_route] += 1,
+                None => break,
+            }
+        }
+
+        let mut leg_amounts: Vec<U256> = chunks
+            .iter()
+            .map(|&route_chunks| amount * U256::from(route_chunks) / U256::from(parts))
+            .collect();
+
+        // @dev rounding leftover goes to the biggest leg so legs add up to amount
+        let allocated = leg_amounts
+            .iter()
+            .fold(U256::zero(), |total, leg_amount| total + leg_amount);
+
+        if let Some(biggest_leg) = (0..chunks.len()).max_by_key(|&i| chunks[i]) {
+            leg_amounts[biggest_leg] += amount - allocated;
+        }
+
+        let mut split_offer = SplitOffer {
+            legs: vec![],
+            amount_out: U256::zero(),
+            net_amount_out: Some(U256::zero()),
+        };
+
+        for (i, route) in distinct_routes.iter().enumerate() {
+            if leg_amounts[i].is_zero() {
+                continue;
+            }
+
+            let leg = Self::quote_route(&router_contract, &route.offer, &adapters, leg_amounts[i])
+                .await
+                .ok_or_else(|| {
+                    ethers::contract::ContractError::ProviderError(ProviderError::CustomError(
+                        "Split leg quote failed".to_owned(),
+                    ))
+                })?;
+
+            let leg_amount_out = *leg.amounts.last().unwrap();
+
+            split_offer.amount_out += leg_amount_out;
+            split_offer.net_amount_out =
+                split_offer
+                    .net_amount_out
+                    .zip(route.net_amount_out)
+                    .map(|(net_amount_out, _)| {
+                        net_amount_out + leg_amount_out.saturating_sub(gas_costs[i])
+                    });
+            split_offer.legs.push(leg);
+        }
+
+        Ok(split_offer)
+    }
+
+    // @dev route output for amount, every hop is queryAdapter of the route adapter
+    async fn quote_route(
+        router_contract: &YakRouter<Provider<Http>>,
+        route: &FormattedOfferWithGas,
+        adapters: &[Adapter],
+        amount: U256,
+    ) -> Option<FormattedOfferWithGas> {
+        let mut amounts = vec![amount];
+
+        for (hop, adapter) in route.adapters.iter().enumerate() {
+            let adapter_index = adapters
+                .iter()
+                .position(|known| known.address == *adapter)?;
+
+            let amount_out = router_contract
+                .query_adapter(
+                    *amounts.last().unwrap(),
+                    route.path[hop],
+                    route.path[hop + 1],
+                    adapter_index as u8,
+                )
+                .call()
+                .await
+                .ok()?;
+
+            if amount_out.is_zero() {
+                return None;
+            }
+
+            amounts.push(amount_out);
+        }
+
+        Some(FormattedOfferWithGas {
+            amounts,
+            adapters: route.adapters.to_owned(),
+            path: route.path.to_owned(),
+            gas_estimate: route.gas_estimate,
+        })
+    }
+
+    // @dev best (amount out, adapter, swapGasEstimate) for every (amount, token in, token out) hop,
+    // @dev None when no adapter has liquidity. Second value is count of queries that failed
+    async fn query_hops(
+        router_contract: Arc<YakRouter<Provider<Http>>>,
+        adapters_gas: &[(H160, U256)],
+        hops: &[(U256, H160, H160)],
+    ) -> (Vec<Option<(U256, H160, U256)>>, usize) {
+        let queries = hops.iter().enumerate().flat_map(|(hop, query)| {
+            (0..adapters_gas.len()).map(move |adapter| (hop, adapter, *query))
+        });
+
+        let amounts_out: Vec<(usize, usize, Option<U256>)> = stream::iter(queries)
+            .map(|(hop, adapter, (amount, token_in, token_out))| {
+                let router_contract = router_contract.clone();
+
+                async move {
+                    let amount_out = router_contract
+                        .query_adapter(amount, token_in, token_out, adapter as u8)
+                        .call()
+                        .await
+                        .ok();
+
+                    (hop, adapter, amount_out)
+                }
+            })
+            .buffer_unordered(Self::MAX_CONCURRENT_QUERIES)
+            .collect()
+            .await;
+
+        let mut best: Vec<Option<(U256, H160, U256)>> = vec![None; hops.len()];
+        let mut failed_queries = 0;
+
+        for (hop, adapter, amount_out) in amounts_out {
+            let amount_out = match amount_out {
+                Some(amount_out) if !amount_out.is_zero() => amount_out,
+                Some(_) => continue,
+                None => {
+                    failed_queries += 1;
+                    continue;
+                }
+            };
+
+            let (adapter, gas_estimate) = adapters_gas[adapter];
+
+            // @dev results come unordered, ties go to the cheaper adapter
+            let is_better = match best[hop] {
+                Some((best_amount_out, _, best_gas_estimate)) => {
+                    amount_out > best_amount_out
+                        || (amount_out == best_amount_out && gas_estimate < best_gas_estimate)
+                }
+                None => true,
+            };
+
+            if is_better {
+                best[hop] = Some((amount_out, adapter, gas_estimate));
+            }
+        }
+
+        (best, failed_queries)
+    }
+
+    // @dev amount of token out for 1 wrapped native, used to price gas
+    async fn get_native_rate(
+        router_contract: &YakRouter<Provider<Http>>,
+        wrapped_native: H160,
+        token_out: H160,
+    ) -> Option<U256> {
+        if token_out == wrapped_native {
+            return Some(U256::exp10(18));
+        }
+
+        router_contract
+            .query_no_split(U256::exp10(18), wrapped_native, token_out)
+            .call()
+            .await
+            .ok()
+            .map(|query| query.amount_out)
+            .filter(|amount_out| !amount_out.is_zero())
+    }
+
+    fn gas_cost_in(gas_cost: U256, native_rate: U256) -> U256 {
+        gas_cost * native_rate / U256::exp10(18)
+    }
+
     #[tokio::main]
     pub async fn find_best_path_with_gas(
         amount: U256,
@@ -145,20 +718,24 @@ impl Query {
 
         let current_network = Network::get_current_network();
         let provider = Arc::new(
-            Provider::<Http>::try_from(current_network.rpc_url)
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
                 .expect("could not instantiate HTTP Provider"),
         );
 
         let mut token_in = token_in;
 
         if Token::is_native(token_in) {
-            token_in = Token::get_native_wrapped(current_network.chain_id);
+            token_in = Token::load_native_wrapped(&current_network)
+                .await
+                .map_err(|err| ethers::contract::ContractError::ProviderError(err.into()))?;
         }
 
         let mut token_out = token_out;
 
         if Token::is_native(token_out) {
-            token_out = Token::get_native_wrapped(current_network.chain_id);
+            token_out = Token::load_native_wrapped(&current_network)
+                .await
+                .map_err(|err| ethers::contract::ContractError::ProviderError(err.into()))?;
         }
 
         if let Some(yak_router_address) = current_network.yak_router {
@@ -208,14 +785,15 @@ impl Query {
 
         let mut token_in = token_in;
 
+        // @dev 1inch takes native token as 0xEeee...EEeE, no need for wrapped address
         if Token::is_native(token_in) {
-            token_in = Token::get_native_wrapped(current_network.chain_id);
+            token_in = Self::ONEINCH_NATIVE.parse::<H160>().unwrap();
         }
 
         let mut token_out = token_out;
 
         if Token::is_native(token_out) {
-            token_out = Token::get_native_wrapped(current_network.chain_id);
+            token_out = Self::ONEINCH_NATIVE.parse::<H160>().unwrap();
         }
 
         // check that network is supported (Avalanche, Optimism, Arbitrum)
@@ -235,39 +813,45 @@ impl Query {
     }
 
     #[tokio::main]
-    pub async fn get_gas_price() -> Result<U256, ProviderError> {
+    pub async fn get_block_number() -> Result<U64, ProviderError> {
         let current_network = Network::get_current_network();
-        let provider = Arc::new(
-            Provider::<Http>::try_from(current_network.rpc_url)
-                .expect("could not instantiate HTTP Provider"),
-        );
+        let provider = Provider::<Http>::try_from(current_network.rpc_url)
+            .expect("could not instantiate HTTP Provider");
 
-        let gas_price = provider.get_gas_price().await?;
-
-        Ok(gas_price)
+        provider.get_block_number().await
     }
 
     #[tokio::main]
-    pub async fn has_permit(token_address: H160) -> bool {
+    pub async fn get_gas_price() -> Result<U256, ProviderError> {
         let current_network = Network::get_current_network();
         let provider = Arc::new(
             Provider::<Http>::try_from(current_network.rpc_url)
                 .expect("could not instantiate HTTP Provider"),
         );
 
-        let contract_code = provider.get_code(token_address, None).await;
+        let gas_price = provider.get_gas_price().await?;
 
-        match contract_code {
-            Ok(contract_code) => {
-                let sig = "d505accf";
+        Ok(gas_price)
+    }
+}
 
-                let has_permit = contract_code.contains(&sig.as_bytes()[0]);
+// @dev explored route, net amount is None when gas cost can't be priced in token out
+pub struct RouteOffer {
+    pub offer: FormattedOfferWithGas,
+    pub net_amount_out: Option<U256>,
+}
 
-                has_permit
-            }
-            Err(_) => false,
-        }
-    }
+// @dev failed queries are adapter hops that reverted or errored, routes through them are missing
+pub struct RouteExploration {
+    pub routes: Vec<RouteOffer>,
+    pub failed_queries: usize,
+}
+
+// @dev legs are separate swapNoSplit trades, amounts are totals over legs
+pub struct SplitOffer {
+    pub legs: Vec<FormattedOfferWithGas>,
+    pub amount_out: U256,
+    pub net_amount_out: Option<U256>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,651 @@
+use std::{fs, sync::Arc};
+
+use ethers::{
+    contract::ContractError,
+    providers::{Http, Provider},
+    types::{transaction::eip2718::TypedTransaction, H160, U256},
+};
+use futures::future;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    abis::{YakAdapter, YakRouter, ERC20},
+    network::Network,
+};
+
+pub struct Router {}
+
+pub enum RouterError {
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    ContractError(ContractError<Provider<Http>>),
+    NoRouter,
+}
+
+impl From<std::io::Error> for RouterError {
+    fn from(err: std::io::Error) -> RouterError {
+        RouterError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for RouterError {
+    fn from(err: serde_json::Error) -> RouterError {
+        RouterError::JsonError(err)
+    }
+}
+
+impl From<ContractError<Provider<Http>>> for RouterError {
+    fn from(err: ContractError<Provider<Http>>) -> RouterError {
+        RouterError::ContractError(err)
+    }
+}
+
+impl std::fmt::Display for RouterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouterError::IoError(err) => write!(f, "file error: {}", err),
+            RouterError::JsonError(err) => write!(f, "wrong snapshot file: {}", err),
+            RouterError::ContractError(err) => write!(f, "contract error: {}", err),
+            RouterError::NoRouter => write!(f, "no Yak Router address for current network"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterAdapter {
+    pub address: H160,
+    pub name: String,
+    pub gas_estimate: U256,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterToken {
+    pub address: H160,
+    pub symbol: String,
+}
+
+// @dev router configuration snapshot, adapters and trusted tokens are in router order
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RouterInfo {
+    pub chain_id: u32,
+    pub address: H160,
+    pub owner: H160,
+    pub fee_claimer: H160,
+    pub min_fee: U256,
+    pub adapters_count: U256,
+    pub adapters: Vec<RouterAdapter>,
+    pub trusted_tokens: Vec<RouterToken>,
+}
+
+impl RouterInfo {
+    // @dev changes from `previous` to self, empty when configuration is the same
+    pub fn diff(&self, previous: &RouterInfo) -> Vec<String> {
+        let mut changes: Vec<String> = vec![];
+
+        if self.chain_id != previous.chain_id || self.address != previous.address {
+            changes.push(format!(
+                "router: {:?} (chain id {}) -> {:?} (chain id {})",
+                previous.address, previous.chain_id, self.address, self.chain_id
+            ));
+        }
+
+        if self.owner != previous.owner {
+            changes.push(format!("owner: {:?} -> {:?}", previous.owner, self.owner));
+        }
+
+        if self.fee_claimer != previous.fee_claimer {
+            changes.push(format!(
+                "FEE_CLAIMER: {:?} -> {:?}",
+                previous.fee_claimer, self.fee_claimer
+            ));
+        }
+
+        if self.min_fee != previous.min_fee {
+            changes.push(format!("MIN_FEE: {} -> {}", previous.min_fee, self.min_fee));
+        }
+
+        for adapter in &previous.adapters {
+            match self.adapters.iter().find(|a| a.address == adapter.address) {
+                Some(current) if current.gas_estimate != adapter.gas_estimate => {
+                    changes.push(format!(
+                        "adapter gas estimate {} ({:?}): {} -> {}",
+                        adapter.name, adapter.address, adapter.gas_estimate, current.gas_estimate
+                    ))
+                }
+                Some(_) => {}
+                None => changes.push(format!(
+                    "- adapter {} ({:?})",
+                    adapter.name, adapter.address
+                )),
+            }
+        }
+
+        for adapter in &self.adapters {
+            if !previous
+                .adapters
+                .iter()
+                .any(|a| a.address == adapter.address)
+            {
+                changes.push(format!(
+                    "+ adapter {} ({:?}), gas estimate {}",
+                    adapter.name, adapter.address, adapter.gas_estimate
+                ));
+            }
+        }
+
+        // @dev queryAdapter takes adapter index, so reordering changes results too
+        if Self::is_reordered(
+            &self
+                .adapters
+                .iter()
+                .map(|a| a.address)
+                .collect::<Vec<H160>>(),
+            &previous
+                .adapters
+                .iter()
+                .map(|a| a.address)
+                .collect::<Vec<H160>>(),
+        ) {
+            changes.push("adapters order changed".to_owned());
+        }
+
+        for token in &previous.trusted_tokens {
+            if !self
+                .trusted_tokens
+                .iter()
+                .any(|t| t.address == token.address)
+            {
+                changes.push(format!(
+                    "- trusted token {} ({:?})",
+                    token.symbol, token.address
+                ));
+            }
+        }
+
+        for token in &self.trusted_tokens {
+            if !previous
+                .trusted_tokens
+                .iter()
+                .any(|t| t.address == token.address)
+            {
+                changes.push(format!(
+                    "+ trusted token {} ({:?})",
+                    token.symbol, token.address
+                ));
+            }
+        }
+
+        // @dev trusted tokens are tried as hops in router order, so reordering can change paths
+        if Self::is_reordered(
+            &self
+                .trusted_tokens
+                .iter()
+                .map(|t| t.address)
+                .collect::<Vec<H160>>(),
+            &previous
+                .trusted_tokens
+                .iter()
+                .map(|t| t.address)
+                .collect::<Vec<H160>>(),
+        ) {
+            changes.push("trusted tokens order changed".to_owned());
+        }
+
+        changes
+    }
+
+    // @dev relative order of addresses in both lists, additions and removals are reported on their own
+    fn is_reordered(current: &[H160], previous: &[H160]) -> bool {
+        let current_shared = current.iter().filter(|address| previous.contains(address));
+        let previous_shared = previous.iter().filter(|address| current.contains(address));
+
+        !current_shared.eq(previous_shared)
+    }
+}
+
+// @dev owner-only router calls, recoverAVAX recovers native token of any network
+pub enum RouterAdminCall {
+    SetAdapters(Vec<H160>),
+    SetTrustedTokens(Vec<H160>),
+    SetMinFee(U256),
+    SetFeeClaimer(H160),
+    RecoverERC20(H160, U256),
+    RecoverAVAX(U256),
+    TransferOwnership(H160),
+}
+
+impl std::fmt::Display for RouterAdminCall {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouterAdminCall::SetAdapters(adapters) => {
+                write!(f, "setAdapters({} adapters)", adapters.len())
+            }
+            RouterAdminCall::SetTrustedTokens(tokens) => {
+                write!(f, "setTrustedTokens({} tokens)", tokens.len())
+            }
+            RouterAdminCall::SetMinFee(fee) => write!(f, "setMinFee({})", fee),
+            RouterAdminCall::SetFeeClaimer(claimer) => write!(f, "setFeeClaimer({:?})", claimer),
+            RouterAdminCall::RecoverERC20(token, amount) => {
+                write!(f, "recoverERC20({:?}, {})", token, amount)
+            }
+            RouterAdminCall::RecoverAVAX(amount) => write!(f, "recoverAVAX({})", amount),
+            RouterAdminCall::TransferOwnership(owner) => {
+                write!(f, "transferOwnership({:?})", owner)
+            }
+        }
+    }
+}
+
+impl RouterAdminCall {
+    // @dev router info as it would be after the call, new adapters and tokens are placeholders,
+    // @dev `Router::preview_admin_call` reads them from chain
+    pub fn apply(&self, info: &RouterInfo) -> RouterInfo {
+        let mut proposed = info.to_owned();
+
+        match self {
+            RouterAdminCall::SetAdapters(adapters) => {
+                proposed.adapters_count = U256::from(adapters.len());
+                proposed.adapters = adapters
+                    .iter()
+                    .map(|address| {
+                        info.adapters
+                            .iter()
+                            .find(|adapter| adapter.address == *address)
+                            .cloned()
+                            .unwrap_or(RouterAdapter {
+                                address: *address,
+                                name: "new adapter".to_owned(),
+                                gas_estimate: U256::zero(),
+                            })
+                    })
+                    .collect();
+            }
+            RouterAdminCall::SetTrustedTokens(tokens) => {
+                proposed.trusted_tokens = tokens
+                    .iter()
+                    .map(|address| {
+                        info.trusted_tokens
+                            .iter()
+                            .find(|token| token.address == *address)
+                            .cloned()
+                            .unwrap_or(RouterToken {
+                                address: *address,
+                                symbol: "new token".to_owned(),
+                            })
+                    })
+                    .collect();
+            }
+            RouterAdminCall::SetMinFee(fee) => proposed.min_fee = *fee,
+            RouterAdminCall::SetFeeClaimer(claimer) => proposed.fee_claimer = *claimer,
+            RouterAdminCall::TransferOwnership(owner) => proposed.owner = *owner,
+            RouterAdminCall::RecoverERC20(_, _) | RouterAdminCall::RecoverAVAX(_) => {}
+        }
+
+        proposed
+    }
+}
+
+impl Router {
+    #[tokio::main]
+    pub async fn get_owner(current_network: Arc<Network>) -> Result<H160, RouterError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let yak_router_address = match &current_network.yak_router {
+            Some(yak_router_address) => yak_router_address
+                .parse::<H160>()
+                .map_err(|_| RouterError::NoRouter)?,
+            None => return Err(RouterError::NoRouter),
+        };
+
+        Ok(YakRouter::new(yak_router_address, provider)
+            .owner()
+            .call()
+            .await?)
+    }
+
+    // @dev applied call with name and swapGasEstimate of new adapters and symbol of new trusted tokens
+    #[tokio::main]
+    pub async fn preview_admin_call(
+        call: &RouterAdminCall,
+        info: &RouterInfo,
+        current_network: Arc<Network>,
+    ) -> RouterInfo {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let mut proposed = call.apply(info);
+
+        proposed.adapters = future::join_all(proposed.adapters.into_iter().map(|adapter| {
+            let provider = provider.clone();
+            let is_new = !info.adapters.iter().any(|a| a.address == adapter.address);
+
+            async move {
+                if !is_new {
+                    return adapter;
+                }
+
+                // @dev not an adapter, shown as UNKNOWN so it stands out in the preview
+                Self::get_adapter(adapter.address, provider)
+                    .await
+                    .unwrap_or(RouterAdapter {
+                        address: adapter.address,
+                        name: "UNKNOWN".to_owned(),
+                        gas_estimate: U256::zero(),
+                    })
+            }
+        }))
+        .await;
+
+        proposed.trusted_tokens =
+            future::join_all(proposed.trusted_tokens.into_iter().map(|token| {
+                let provider = provider.clone();
+                let is_new = !info
+                    .trusted_tokens
+                    .iter()
+                    .any(|t| t.address == token.address);
+
+                async move {
+                    if !is_new {
+                        return token;
+                    }
+
+                    Self::get_token(token.address, provider).await
+                }
+            }))
+            .await;
+
+        proposed
+    }
+
+    // @dev admin call without signer, it's simulated, sent or exported to Safe batch
+    pub fn build_admin_tx(
+        call: &RouterAdminCall,
+        router: H160,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let router_contract = YakRouter::new(router, provider);
+
+        match call {
+            RouterAdminCall::SetAdapters(adapters) => {
+                router_contract.set_adapters(adapters.to_owned()).tx
+            }
+            RouterAdminCall::SetTrustedTokens(tokens) => {
+                router_contract.set_trusted_tokens(tokens.to_owned()).tx
+            }
+            RouterAdminCall::SetMinFee(fee) => router_contract.set_min_fee(*fee).tx,
+            RouterAdminCall::SetFeeClaimer(claimer) => router_contract.set_fee_claimer(*claimer).tx,
+            RouterAdminCall::RecoverERC20(token, amount) => {
+                router_contract.recover_erc20(*token, *amount).tx
+            }
+            RouterAdminCall::RecoverAVAX(amount) => router_contract.recover_avax(*amount).tx,
+            RouterAdminCall::TransferOwnership(owner) => {
+                router_contract.transfer_ownership(*owner).tx
+            }
+        }
+    }
+
+    #[tokio::main]
+    pub async fn get_info(current_network: Arc<Network>) -> Result<RouterInfo, RouterError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let yak_router_address = match &current_network.yak_router {
+            Some(yak_router_address) => yak_router_address
+                .parse::<H160>()
+                .map_err(|_| RouterError::NoRouter)?,
+            None => return Err(RouterError::NoRouter),
+        };
+
+        let router_contract = Arc::new(YakRouter::new(yak_router_address, provider.clone()));
+
+        let owner = router_contract.owner().call().await?;
+        let fee_claimer = router_contract.fee_claimer().call().await?;
+        let min_fee = router_contract.min_fee().call().await?;
+        let adapters_count = router_contract.adapters_count().call().await?;
+        let trusted_tokens_count = router_contract.trusted_tokens_count().call().await?;
+
+        let adapters = future::try_join_all((0..adapters_count.as_u32()).map(|i| {
+            let router_contract = router_contract.clone();
+            let provider = provider.clone();
+
+            async move {
+                let address = router_contract.adapters(U256::from(i)).call().await?;
+
+                Self::get_adapter(address, provider).await
+            }
+        }))
+        .await?;
+
+        let trusted_tokens = future::try_join_all((0..trusted_tokens_count.as_u32()).map(|i| {
+            let router_contract = router_contract.clone();
+            let provider = provider.clone();
+
+            async move {
+                let address = router_contract.trusted_tokens(U256::from(i)).call().await?;
+
+                Ok::<RouterToken, ContractError<Provider<Http>>>(
+                    Self::get_token(address, provider).await,
+                )
+            }
+        }))
+        .await?;
+
+        Ok(RouterInfo {
+            chain_id: current_network.chain_id,
+            address: yak_router_address,
+            owner,
+            fee_claimer,
+            min_fee,
+            adapters_count,
+            adapters,
+            trusted_tokens,
+        })
+    }
+
+    async fn get_adapter(
+        address: H160,
+        provider: Arc<Provider<Http>>,
+    ) -> Result<RouterAdapter, ContractError<Provider<Http>>> {
+        let adapter_contract = YakAdapter::new(address, provider);
+
+        let name = adapter_contract.name().call().await?;
+        let gas_estimate = adapter_contract.swap_gas_estimate().call().await?;
+
+        Ok(RouterAdapter {
+            address,
+            name,
+            gas_estimate,
+        })
+    }
+
+    async fn get_token(address: H160, provider: Arc<Provider<Http>>) -> RouterToken {
+        let symbol = ERC20::new(address, provider)
+            .symbol()
+            .call()
+            .await
+            .unwrap_or_else(|_| "UNKNOWN".to_owned());
+
+        RouterToken { address, symbol }
+    }
+
+    pub fn export_info(info: &RouterInfo, path: &str) -> Result<(), RouterError> {
+        let json = serde_json::to_string_pretty(info)?;
+
+        fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    pub fn import_info(path: &str) -> Result<RouterInfo, RouterError> {
+        let content = fs::read_to_string(path)?;
+
+        Ok(serde_json::from_str::<RouterInfo>(&content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(index: u64, gas_estimate: u64) -> RouterAdapter {
+        RouterAdapter {
+            address: H160::from_low_u64_be(index),
+            name: format!("Adapter{}", index),
+            gas_estimate: U256::from(gas_estimate),
+        }
+    }
+
+    fn token(index: u64) -> RouterToken {
+        RouterToken {
+            address: H160::from_low_u64_be(100 + index),
+            symbol: format!("TKN{}", index),
+        }
+    }
+
+    fn info() -> RouterInfo {
+        RouterInfo {
+            chain_id: 43114,
+            address: H160::from_low_u64_be(1000),
+            owner: H160::from_low_u64_be(1001),
+            fee_claimer: H160::from_low_u64_be(1002),
+            min_fee: U256::zero(),
+            adapters_count: U256::from(2),
+            adapters: vec![adapter(1, 100_000), adapter(2, 200_000)],
+            trusted_tokens: vec![token(1), token(2)],
+        }
+    }
+
+    #[test]
+    fn diff_is_empty_for_same_configuration() {
+        assert!(info().diff(&info()).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_owner_fee_and_gas_estimate_changes() {
+        let previous = info();
+        let mut current = info();
+
+        current.owner = H160::from_low_u64_be(2001);
+        current.min_fee = U256::from(5);
+        current.adapters[1].gas_estimate = U256::from(250_000);
+
+        let changes = current.diff(&previous);
+
+        assert_eq!(changes.len(), 3);
+        assert!(changes[0].starts_with("owner:"));
+        assert_eq!(changes[1], "MIN_FEE: 0 -> 5");
+        assert!(changes[2].starts_with("adapter gas estimate Adapter2"));
+        assert!(changes[2].ends_with("200000 -> 250000"));
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_entries() {
+        let previous = info();
+        let mut current = info();
+
+        current.adapters = vec![adapter(1, 100_000), adapter(3, 300_000)];
+        current.trusted_tokens = vec![token(2), token(3)];
+
+        let changes = current.diff(&previous);
+
+        assert_eq!(
+            changes,
+            vec![
+                format!("- adapter Adapter2 ({:?})", H160::from_low_u64_be(2)),
+                format!(
+                    "+ adapter Adapter3 ({:?}), gas estimate 300000",
+                    H160::from_low_u64_be(3)
+                ),
+                format!("- trusted token TKN1 ({:?})", H160::from_low_u64_be(101)),
+                format!("+ trusted token TKN3 ({:?})", H160::from_low_u64_be(103)),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_adapters_reorder() {
+        let previous = info();
+        let mut current = info();
+
+        current.adapters.reverse();
+
+        assert_eq!(current.diff(&previous), vec!["adapters order changed"]);
+    }
+
+    #[test]
+    fn diff_reports_trusted_tokens_reorder() {
+        let previous = info();
+        let mut current = info();
+
+        current.trusted_tokens.reverse();
+
+        assert_eq!(
+            current.diff(&previous),
+            vec!["trusted tokens order changed"]
+        );
+    }
+
+    #[test]
+    fn diff_reports_reorder_with_added_token() {
+        let previous = info();
+        let mut current = info();
+
+        current.trusted_tokens = vec![token(2), token(1), token(3)];
+
+        assert_eq!(
+            current.diff(&previous),
+            vec![
+                format!("+ trusted token TKN3 ({:?})", H160::from_low_u64_be(103)),
+                "trusted tokens order changed".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_reports_reorder_with_removed_adapter() {
+        let mut previous = info();
+        let mut current = info();
+
+        previous.adapters.push(adapter(3, 300_000));
+        current.adapters = vec![adapter(3, 300_000), adapter(1, 100_000)];
+
+        assert_eq!(
+            current.diff(&previous),
+            vec![
+                format!("- adapter Adapter2 ({:?})", H160::from_low_u64_be(2)),
+                "adapters order changed".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_does_not_report_reorder_when_only_appending() {
+        let previous = info();
+        let mut current = info();
+
+        current.trusted_tokens.push(token(3));
+
+        assert_eq!(
+            current.diff(&previous),
+            vec![format!(
+                "+ trusted token TKN3 ({:?})",
+                H160::from_low_u64_be(103)
+            )]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    fs,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ethers::{
+    abi::{self, Address},
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Bytes, H160, H256, U256},
+    utils::keccak256,
+};
+use serde::Serialize;
+
+use crate::{abis::GnosisSafe, network::Network};
+
+// @dev MultiSendCallOnly deployments by Safe version (v1.3.0 canonical and eip155, v1.4.1),
+// @dev first one with code on the network is used
+const MULTI_SEND_CALL_ONLY: [(&str, &[&str]); 2] = [
+    (
+        "1.3.0",
+        &[
+            "0x40A2aCCbd92BCA938b02010E17A5b8929b49130D",
+            "0xA1dabEF33b3B82c7814B6D82A79e50F4AC44102B",
+        ],
+    ),
+    ("1.4.1", &["0x9641d764fc13c8B624c04430C7356C1C7C8102e2"]),
+];
+
+pub struct Safe {}
+
+#[derive(Clone)]
+pub struct SafeTransaction {
+    pub to: H160,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+impl From<TypedTransaction> for SafeTransaction {
+    fn from(tx: TypedTransaction) -> Self {
+        Self {
+            to: *tx.to().and_then(|to| to.as_address()).unwrap(),
+            value: tx.value().cloned().unwrap_or_default(),
+            data: tx.data().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+pub struct SafeInfo {
+    pub version: String,
+    pub nonce: U256,
+    pub threshold: U256,
+    pub owners: Vec<H160>,
+    // @dev read from the Safe, its domain differs between versions (no chainId before 1.3.0)
+    pub domain_separator: [u8; 32],
+    pub multi_send: Option<H160>,
+}
+
+// @dev Safe Transaction Builder batch file format
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatch {
+    pub version: String,
+    pub chain_id: String,
+    pub created_at: u128,
+    pub meta: SafeBatchMeta,
+    pub transactions: Vec<SafeBatchTransaction>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchMeta {
+    pub name: String,
+    pub description: String,
+    pub tx_builder_version: String,
+    pub created_from_safe_address: String,
+    pub created_from_owner_address: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchTransaction {
+    pub to: String,
+    pub value: String,
+    pub data: String,
+    pub contract_method: Option<()>,
+    pub contract_inputs_values: Option<()>,
+}
+
+impl Safe {
+    #[tokio::main]
+    pub async fn get_info(
+        safe: H160,
+        current_network: Arc<Network>,
+    ) -> Result<SafeInfo, ethers::contract::ContractError<Provider<Http>>> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let safe_contract = GnosisSafe::new(safe, provider.clone());
+
+        let version = safe_contract.version().call().await?;
+
+        // @dev L2 Safes report e.g. "1.3.0+L2" and use the same MultiSendCallOnly
+        let deployments = MULTI_SEND_CALL_ONLY
+            .iter()
+            .find(|(multi_send_version, _)| version.split('+').next() == Some(*multi_send_version))
+            .map(|(_, addresses)| *addresses)
+            .unwrap_or_default();
+
+        let mut multi_send = None;
+
+        for address in deployments {
+            let address = address.parse::<H160>().unwrap();
+
+            let code = provider
+                .get_code(address, None)
+                .await
+                .map_err(ethers::contract::ContractError::MiddlewareError)?;
+
+            if !code.is_empty() {
+                multi_send = Some(address);
+                break;
+            }
+        }
+
+        Ok(SafeInfo {
+            version,
+            nonce: safe_contract.nonce().call().await?,
+            threshold: safe_contract.get_threshold().call().await?,
+            owners: safe_contract.get_owners().call().await?,
+            domain_separator: safe_contract.domain_separator().call().await?,
+            multi_send,
+        })
+    }
+
+    pub fn build_batch(
+        safe: H160,
+        chain_id: u32,
+        description: &str,
+        transactions: &[SafeTransaction],
+    ) -> SafeBatch {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+
+        SafeBatch {
+            version: "1.0".to_owned(),
+            chain_id: chain_id.to_string(),
+            created_at,
+            meta: SafeBatchMeta {
+                name: "Transactions Batch".to_owned(),
+                description: description.to_owned(),
+                tx_builder_version: "1.13.3".to_owned(),
+                created_from_safe_address: format!("{:?}", safe),
+                created_from_owner_address: String::default(),
+            },
+            transactions: transactions
+                .iter()
+                .map(|tx| SafeBatchTransaction {
+                    to: format!("{:?}", tx.to),
+                    value: tx.value.to_string(),
+                    data: format!("{}", tx.data),
+                    contract_method: None,
+                    contract_inputs_values: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn export_batch(batch: &SafeBatch, path: &str) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(batch).unwrap();
+
+        fs::write(path, json)
+    }
+
+    // @dev a single transaction is executed as is, a batch goes through MultiSendCallOnly.
+    // @dev None for a batch when Safe version or network has no known MultiSendCallOnly
+    pub fn safe_tx_hash(safe_info: &SafeInfo, transactions: &[SafeTransaction]) -> Option<H256> {
+        let (to, value, data, operation) = if transactions.len() == 1 {
+            let tx = &transactions[0];
+            (tx.to, tx.value, tx.data.to_vec(), 0u8)
+        } else {
+            (
+                safe_info.multi_send?,
+                U256::zero(),
+                Self::encode_multi_send(transactions),
+                1u8,
+            )
+        };
+
+        // Typehash for the SafeTx struct
+        let safe_tx_typehash = keccak256(
+            "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)",
+        );
+
+        let struct_hash = keccak256(abi::encode(&[
+            ethers::abi::Token::Uint(U256::from(safe_tx_typehash)),
+            ethers::abi::Token::Address(to),
+            ethers::abi::Token::Uint(value),
+            ethers::abi::Token::Uint(U256::from(keccak256(data))),
+            ethers::abi::Token::Uint(U256::from(operation)),
+            // safeTxGas, baseGas, gasPrice, gasToken, refundReceiver
+            ethers::abi::Token::Uint(U256::zero()),
+            ethers::abi::Token::Uint(U256::zero()),
+            ethers::abi::Token::Uint(U256::zero()),
+            ethers::abi::Token::Address(Address::zero()),
+            ethers::abi::Token::Address(Address::zero()),
+            ethers::abi::Token::Uint(safe_info.nonce),
+        ]));
+
+        let digest_input = [
+            &[0x19, 0x01],
+            safe_info.domain_separator.as_ref(),
+            struct_hash.as_ref(),
+        ]
+        .concat();
+
+        Some(<H256>::from(keccak256(&digest_input)))
+    }
+
+    // @dev multiSend(bytes) calldata, every tx is packed as operation, to, value, data length, data
+    fn encode_multi_send(transactions: &[SafeTransaction]) -> Vec<u8> {
+        let mut packed: Vec<u8> = vec![];
+
+        for tx in transactions {
+            let mut value = [0u8; 32];
+            tx.value.to_big_endian(&mut value);
+            let mut data_len = [0u8; 32];
+            U256::from(tx.data.len()).to_big_endian(&mut data_len);
+
+            packed.push(0u8);
+            packed.extend_from_slice(tx.to.as_bytes());
+            packed.extend_from_slice(&value);
+            packed.extend_from_slice(&data_len);
+            packed.extend_from_slice(&tx.data);
+        }
+
+        let selector = &keccak256("multiSend(bytes)")[0..4];
+
+        [
+            selector,
+            abi::encode(&[ethers::abi::Token::Bytes(packed)]).as_ref(),
+        ]
+        .concat()
+    }
+}
@@ -1,11 +1,50 @@
+use std::fmt;
+
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
 use crate::db::DB;
 
 pub struct Settings {}
 
+// @dev how much router is allowed to spend when allowance is not enough for a swap
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum ApprovalPolicy {
+    Exact,
+    // @dev buffer in percent on top of swap amount
+    ExactWithBuffer(u32),
+    Unlimited,
+}
+
+impl fmt::Display for ApprovalPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalPolicy::Exact => write!(f, "Exact amount"),
+            ApprovalPolicy::ExactWithBuffer(buffer) => write!(f, "Exact amount + {}%", buffer),
+            ApprovalPolicy::Unlimited => write!(f, "Unlimited"),
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    pub fn amount(&self, swap_amount: U256) -> U256 {
+        match self {
+            ApprovalPolicy::Exact => swap_amount,
+            ApprovalPolicy::ExactWithBuffer(buffer) => swap_amount
+                .saturating_add(swap_amount.saturating_mul(U256::from(*buffer)) / U256::from(100)),
+            ApprovalPolicy::Unlimited => U256::MAX,
+        }
+    }
+}
+
 impl Settings {
     pub const DB_MAX_STEPS: &'static str = "max_steps";
     pub const DB_SLIPPAGE: &'static str = "slippage";
     pub const DB_COMPARE_WITH_EXTERNAL: &'static str = "is_external_allowed";
+    pub const DB_CACHE_TTL: &'static str = "cache_ttl";
+    pub const DB_OFFLINE_MODE: &'static str = "is_offline";
+    pub const DB_APPROVAL_POLICY: &'static str = "approval_policy";
+    pub const DB_PERMIT_DEADLINE: &'static str = "permit_deadline";
 
     pub fn set_max_steps(steps: i32) {
         let mut db_instance = DB.lock().unwrap();
@@ -51,4 +90,69 @@ impl Settings {
             .set(Settings::DB_COMPARE_WITH_EXTERNAL, &is_allowed)
             .unwrap();
     }
+
+    pub fn set_cache_ttl(minutes: u32) {
+        let mut db_instance = DB.lock().unwrap();
+
+        db_instance.set(Settings::DB_CACHE_TTL, &minutes).unwrap();
+    }
+
+    // @dev cache TTL in minutes for token and adapter lists
+    pub fn get_cache_ttl() -> u32 {
+        let db_instance = DB.lock().unwrap();
+
+        let cache_ttl = db_instance.get::<u32>(Settings::DB_CACHE_TTL);
+
+        cache_ttl.unwrap_or(60)
+    }
+
+    pub fn is_offline() -> bool {
+        let db_instance = DB.lock().unwrap();
+
+        let is_offline = db_instance.get::<bool>(Settings::DB_OFFLINE_MODE);
+
+        is_offline.unwrap_or(false)
+    }
+
+    pub fn set_is_offline(is_offline: bool) {
+        let mut db_instance = DB.lock().unwrap();
+
+        db_instance
+            .set(Settings::DB_OFFLINE_MODE, &is_offline)
+            .unwrap();
+    }
+
+    pub fn set_approval_policy(approval_policy: ApprovalPolicy) {
+        let mut db_instance = DB.lock().unwrap();
+
+        db_instance
+            .set(Settings::DB_APPROVAL_POLICY, &approval_policy)
+            .unwrap();
+    }
+
+    // @dev unlimited by default, same as before policy was configurable
+    pub fn get_approval_policy() -> ApprovalPolicy {
+        let db_instance = DB.lock().unwrap();
+
+        let approval_policy = db_instance.get::<ApprovalPolicy>(Settings::DB_APPROVAL_POLICY);
+
+        approval_policy.unwrap_or(ApprovalPolicy::Unlimited)
+    }
+
+    pub fn set_permit_deadline(minutes: u32) {
+        let mut db_instance = DB.lock().unwrap();
+
+        db_instance
+            .set(Settings::DB_PERMIT_DEADLINE, &minutes)
+            .unwrap();
+    }
+
+    // @dev permit deadline in minutes from latest block timestamp
+    pub fn get_permit_deadline() -> u32 {
+        let db_instance = DB.lock().unwrap();
+
+        let permit_deadline = db_instance.get::<u32>(Settings::DB_PERMIT_DEADLINE);
+
+        permit_deadline.unwrap_or(20)
+    }
 }
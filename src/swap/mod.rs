@@ -1,20 +1,24 @@
 use std::{
+    fmt,
     ops::{Mul, Sub},
+    panic::{self, AssertUnwindSafe},
     sync::Arc,
 };
 
 use ethers::{
-    abi::{self, Address},
     prelude::{k256::ecdsa::SigningKey, SignerMiddleware},
-    providers::{Http, Provider},
+    providers::{Http, Middleware, Provider, ProviderError},
     signers::{Signer, Wallet},
-    types::{TransactionReceipt, H160, H256, U256},
-    utils::keccak256,
+    types::{
+        transaction::eip2718::TypedTransaction, TransactionReceipt, TransactionRequest, H160, U256,
+        U64,
+    },
 };
 
 use crate::{
     abis::{Trade, YakRouter, ERC20, IWETH},
     network::Network,
+    permit::{Permit, PermitDomain},
     settings::Settings,
     token::Token,
 };
@@ -27,6 +31,23 @@ pub enum FromToNative {
 
 pub struct Swap {}
 
+// @dev errors of unattended swaps (limit orders, DCA), they must not panic the watcher loop
+pub enum SwapError {
+    SimulationFailed(ProviderError),
+    SendFailed(String),
+    NoReceipt,
+}
+
+impl fmt::Display for SwapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapError::SimulationFailed(err) => write!(f, "simulation failed: {}", err),
+            SwapError::SendFailed(err) => write!(f, "send failed: {}", err),
+            SwapError::NoReceipt => write!(f, "no receipt for swap tx"),
+        }
+    }
+}
+
 impl Swap {
     #[tokio::main]
     pub async fn swap_no_split(
@@ -100,9 +121,12 @@ impl Swap {
         }
     }
 
+    // @dev deadline is checked together with signature before sending
     #[tokio::main]
     pub async fn swap_no_split_with_permit(
         mut trade: Trade,
+        permit_domain: &PermitDomain,
+        deadline: U256,
         to: H160,
         from_to_native: Option<FromToNative>,
         signer: &Wallet<SigningKey>,
@@ -133,69 +157,42 @@ impl Swap {
 
         trade.handle_slippage_setting();
 
-        let default_deadline = U256::MAX;
-
         let nonce_count = token_in_contract
             .nonces(signer.address())
             .call()
             .await
             .expect("Expect to get nonce");
 
-        let owner: Address = signer.address();
-        let spender: Address = current_network
+        let spender: H160 = current_network
             .yak_router
             .as_ref()
             .unwrap()
             .parse::<H160>()
             .unwrap();
-        let value = trade.amount_in;
-        let nonce = nonce_count;
-        let deadline = default_deadline;
-        let verifying_contract: Address = trade.path[0];
-        let name = "Yak Token";
-        let version = "1";
-        let chainid = current_network.chain_id;
-
-        // Typehash for the permit() function
-        let permit_typehash = keccak256(
-            "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
-        );
-        // Typehash for the struct used to generate the domain separator
-        let domain_typehash = keccak256(
-            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+
+        let struct_hash = Permit::eip2612_struct_hash(
+            signer.address(),
+            spender,
+            trade.amount_in,
+            nonce_count,
+            deadline,
         );
 
-        // Corresponds to solidity's abi.encode()
-        let domain_separator_input = abi::encode(&[
-            ethers::abi::Token::Uint(U256::from(domain_typehash)),
-            ethers::abi::Token::Uint(U256::from(keccak256(name))),
-            ethers::abi::Token::Uint(U256::from(keccak256(version))),
-            ethers::abi::Token::Uint(U256::from(chainid)),
-            ethers::abi::Token::Address(verifying_contract),
-        ]);
-
-        let domain_separator = keccak256(&domain_separator_input);
-
-        let struct_input = abi::encode(&vec![
-            ethers::abi::Token::Uint(U256::from(permit_typehash)),
-            ethers::abi::Token::Address(owner),
-            ethers::abi::Token::Address(spender),
-            ethers::abi::Token::Uint(value),
-            ethers::abi::Token::Uint(nonce),
-            ethers::abi::Token::Uint(deadline),
-        ]);
-        let struct_hash = keccak256(&struct_input);
-
-        let digest_input = [
-            &[0x19, 0x01],
-            domain_separator.as_ref(),
-            struct_hash.as_ref(),
-        ]
-        .concat();
-
-        let permit_hash = <H256>::from(keccak256(&digest_input));
-
-        let signature = signer.sign_hash(permit_hash);
+        let signature = Permit::sign(permit_domain, struct_hash, signer);
+
+        if let Err(err) = Permit::validate(
+            provider.as_ref(),
+            permit_domain,
+            struct_hash,
+            &signature,
+            signer.address(),
+            deadline,
+        )
+        .await
+        {
+            println!("Swap is not sent: {}", err);
+            return None;
+        }
 
         // if trade path starts from avax swap_no_split_from_avax
         // else if trade path to avax swap_no_split_to_avax
@@ -221,7 +218,7 @@ impl Swap {
                         trade,
                         to,
                         U256::from(0),
-                        default_deadline,
+                        deadline,
                         signature.v as u8,
                         <[u8; 32]>::from(signature.r),
                         <[u8; 32]>::from(signature.s),
@@ -241,7 +238,7 @@ impl Swap {
                 trade,
                 to,
                 U256::from(0),
-                default_deadline,
+                deadline,
                 signature.v as u8,
                 <[u8; 32]>::from(signature.r),
                 <[u8; 32]>::from(signature.s),
@@ -256,9 +253,185 @@ impl Swap {
         }
     }
 
+    // @dev DAI permit tx, swap, then permit revoke so no unlimited allowance stays after swap, even a failed one
+    pub fn swap_no_split_with_dai_permit(
+        trade: Trade,
+        permit_domain: &PermitDomain,
+        deadline: U256,
+        to: H160,
+        from_to_native: Option<FromToNative>,
+        signer: &Wallet<SigningKey>,
+        current_network: Arc<Network>,
+    ) -> Option<TransactionReceipt> {
+        let token_address = trade.path[0];
+
+        let spender = current_network
+            .yak_router
+            .as_ref()
+            .unwrap()
+            .parse::<H160>()
+            .unwrap();
+
+        let permit_receipt = Permit::send_dai_permit(
+            token_address,
+            permit_domain,
+            spender,
+            deadline,
+            true,
+            signer,
+            current_network.clone(),
+        );
+
+        match permit_receipt {
+            Some(permit_receipt) if permit_receipt.status != Some(U64::zero()) => {
+                println!("Permit TX Hash: {:?}", permit_receipt.transaction_hash);
+            }
+            _ => {
+                println!("Permit transaction failed");
+                return None;
+            }
+        }
+
+        // @dev swap panics when it fails to send, revoke is still sent before the panic goes on
+        let swap_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            Self::swap_no_split(trade, to, from_to_native, signer, current_network.clone())
+        }));
+
+        // @dev swap may outlast permit deadline, revoke gets a fresh one
+        let revoke_receipt = match Permit::get_deadline(current_network.clone()) {
+            Ok(revoke_deadline) => Permit::send_dai_permit(
+                token_address,
+                permit_domain,
+                spender,
+                revoke_deadline,
+                false,
+                signer,
+                current_network,
+            ),
+            Err(_) => None,
+        };
+
+        match revoke_receipt {
+            Some(revoke_receipt) if revoke_receipt.status != Some(U64::zero()) => {
+                println!(
+                    "Permit revoke TX Hash: {:?}",
+                    revoke_receipt.transaction_hash
+                );
+            }
+            _ => println!("Permit revoke failed, router still has unlimited allowance"),
+        }
+
+        match swap_result {
+            Ok(swap_receipt) => swap_receipt,
+            Err(swap_panic) => panic::resume_unwind(swap_panic),
+        }
+    }
+
+    // @dev same call as swap_no_split but without signer, used for offline signing
+    pub fn build_swap_no_split_tx(
+        mut trade: Trade,
+        to: H160,
+        from_to_native: Option<FromToNative>,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        trade.handle_slippage_setting();
+
+        Self::build_trade_tx(trade, to, from_to_native, current_network)
+    }
+
+    // @dev trade amount out is used as is, e.g. limit order floor
+    pub fn build_trade_tx(
+        trade: Trade,
+        to: H160,
+        from_to_native: Option<FromToNative>,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let yak_router_contract = YakRouter::new(
+            current_network
+                .yak_router
+                .as_ref()
+                .unwrap()
+                .parse::<H160>()
+                .unwrap(),
+            provider,
+        );
+
+        match from_to_native {
+            Some(FromToNative::FromNative) => {
+                let value_amount = trade.amount_in;
+
+                yak_router_contract
+                    .swap_no_split_from_avax(trade, to, U256::from(0))
+                    .value(value_amount)
+                    .tx
+            }
+            Some(FromToNative::ToNative) => {
+                yak_router_contract
+                    .swap_no_split_to_avax(trade, to, U256::from(0))
+                    .tx
+            }
+            None => {
+                yak_router_contract
+                    .swap_no_split(trade, to, U256::from(0))
+                    .tx
+            }
+        }
+    }
+
+    // @dev gas estimate fails like eth_call when the swap would revert
+    #[tokio::main]
+    pub async fn estimate_tx_cost(
+        tx: TypedTransaction,
+        current_network: Arc<Network>,
+    ) -> Result<U256, ProviderError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        let gas = provider.estimate_gas(&tx, None).await?;
+        let gas_price = provider.get_gas_price().await?;
+
+        Ok(gas * gas_price)
+    }
+
+    // @dev eth_call first, tx is only sent when the swap would succeed at current state
+    #[tokio::main]
+    pub async fn send_simulated(
+        tx: TypedTransaction,
+        signer: &Wallet<SigningKey>,
+        current_network: Arc<Network>,
+    ) -> Result<TransactionReceipt, SwapError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        provider
+            .call(&tx, None)
+            .await
+            .map_err(SwapError::SimulationFailed)?;
+
+        let provider = SignerMiddleware::new_with_provider_chain(provider, signer.to_owned())
+            .await
+            .map_err(|err| SwapError::SendFailed(err.to_string()))?;
+
+        let pending_tx = provider
+            .send_transaction(tx, None)
+            .await
+            .map_err(|err| SwapError::SendFailed(err.to_string()))?;
+
+        pending_tx
+            .await
+            .map_err(|err| SwapError::SendFailed(err.to_string()))?
+            .ok_or(SwapError::NoReceipt)
+    }
+
     #[tokio::main]
     pub async fn wrap_native(
         amount_in: U256,
+        wrapped_native: H160,
         signer: &Wallet<SigningKey>,
         current_network: Arc<Network>,
     ) -> Option<TransactionReceipt> {
@@ -273,9 +446,7 @@ impl Swap {
                 .unwrap(),
         );
 
-        let native_address = Token::get_native_wrapped(current_network.chain_id);
-
-        let wrap_contract = IWETH::new(native_address, provider);
+        let wrap_contract = IWETH::new(wrapped_native, provider);
 
         let call = wrap_contract.deposit().value(amount_in);
         let pending_tx = call.send().await.expect("Error when wrap deposit call");
@@ -285,12 +456,15 @@ impl Swap {
             .expect("Error while getting confirmations on wrap deposit")
     }
 
+    // @dev WETH withdraw always pays msg.sender, so native is forwarded when `to` is another address
     #[tokio::main]
     pub async fn unwrap_native(
         amount_in: U256,
+        wrapped_native: H160,
+        to: H160,
         signer: &Wallet<SigningKey>,
         current_network: Arc<Network>,
-    ) -> Option<TransactionReceipt> {
+    ) -> Result<TransactionReceipt, &'static str> {
         let provider = Arc::new(
             Provider::<Http>::try_from(current_network.rpc_url.to_owned())
                 .expect("could not instantiate HTTP Provider"),
@@ -302,16 +476,41 @@ impl Swap {
                 .unwrap(),
         );
 
-        let native_address = Token::get_native_wrapped(current_network.chain_id);
-
-        let wrap_contract = IWETH::new(native_address, provider);
+        let wrap_contract = IWETH::new(wrapped_native, provider.clone());
 
         let call = wrap_contract.withdraw(amount_in);
         let pending_tx = call.send().await.expect("Error when wrap withdraw call");
 
-        pending_tx
+        let receipt = pending_tx
             .await
             .expect("Error while getting confirmations on wrap withdraw")
+            .ok_or("Error when getting tx hash on unwrap")?;
+
+        // @dev native is forwarded only after withdraw succeeded
+        if receipt.status != Some(1.into()) {
+            return Err("Unwrap failed, native transfer not sent");
+        }
+
+        if to == signer.address() {
+            return Ok(receipt);
+        }
+
+        let transfer_tx = TransactionRequest::pay(to, amount_in);
+        let pending_tx = provider
+            .send_transaction(transfer_tx, None)
+            .await
+            .expect("Error when native transfer call");
+
+        let receipt = pending_tx
+            .await
+            .expect("Error while getting confirmations on native transfer")
+            .ok_or("Error when getting tx hash on native transfer")?;
+
+        if receipt.status != Some(1.into()) {
+            return Err("Native transfer to recipient failed");
+        }
+
+        Ok(receipt)
     }
 
     pub fn decide_from_to_native(address_from: H160, address_to: H160) -> Option<FromToNative> {
@@ -329,15 +528,20 @@ impl Swap {
 
 impl Trade {
     pub fn handle_slippage_setting(&mut self) {
-        // @dev e.g. 5 = 0.5%
-        let slippage = Settings::get_slippage();
+        self.handle_slippage(Settings::get_slippage());
+    }
 
-        let amount_out_with_slippage = self.amount_out.sub(
-            self.amount_out
+    // @dev e.g. 5 = 0.5%
+    pub fn handle_slippage(&mut self, slippage: u32) {
+        self.amount_out = Self::apply_slippage(self.amount_out, slippage);
+    }
+
+    pub fn apply_slippage(amount_out: U256, slippage: u32) -> U256 {
+        amount_out.sub(
+            amount_out
                 .checked_div(U256::from(1000))
                 .unwrap()
                 .mul(U256::from(slippage)),
-        );
-        self.amount_out = amount_out_with_slippage;
+        )
     }
 }
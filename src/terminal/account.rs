@@ -1,6 +1,7 @@
+use crate::wallet::storage::WalletStorage;
 use crate::wallet::AccountWallet;
 use crate::Terminal;
-use console::Term;
+use console::{style, Term};
 use dialoguer::{theme::ColorfulTheme, Input, Password, Select};
 use ethers::prelude::k256::elliptic_curve::Error;
 use num_derive::FromPrimitive;
@@ -11,6 +12,7 @@ pub struct AccountScreen {}
 #[derive(FromPrimitive)]
 enum AccountTopics {
     Add,
+    AddWatchOnly,
     Remove,
     Set,
     Back,
@@ -20,8 +22,9 @@ impl AccountScreen {
     pub fn render() {
         let topics = [
             "1. Add account",
-            "2. Remove account",
-            "3. Set current account",
+            "2. Add watch-only account",
+            "3. Remove account",
+            "4. Set current account",
             "<- Go back",
         ];
 
@@ -42,6 +45,10 @@ impl AccountScreen {
                     Self::add_account().unwrap();
                     Terminal::render();
                 }
+                Some(AccountTopics::AddWatchOnly) => {
+                    Self::add_watch_only_account();
+                    Terminal::render();
+                }
                 Some(AccountTopics::Remove) => {
                     Self::remove_account();
                     Terminal::render();
@@ -80,6 +87,27 @@ impl AccountScreen {
         Ok(account)
     }
 
+    fn add_watch_only_account() {
+        let label: String = Input::new()
+            .with_prompt("Account Label")
+            .interact_text()
+            .unwrap();
+        let address: String = Input::new()
+            .with_prompt("Address")
+            .validate_with(|input: &String| -> Result<(), &str> {
+                AccountWallet::parse_address(input).map(|_| ())
+            })
+            .interact_text()
+            .unwrap();
+
+        WalletStorage::save_watch_only(&label, AccountWallet::parse_address(&address).unwrap());
+
+        println!(
+            "{}",
+            style("Watch-only account successfully added!").green()
+        );
+    }
+
     fn set_account() {
         AccountWallet::set_wallet();
     }
@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use crate::adapter::{AdapterAdmin, AdapterAdminCall, OwnedAdapter};
+use crate::admin::Admin;
+use crate::benchmark::Benchmark;
+use crate::network::Network;
+use crate::query::Query;
+use crate::token::Token;
+use crate::wallet::storage::WalletStorage;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use ethers::types::U256;
+use spinners::{Spinner, Spinners};
+
+use super::router::RouterScreen;
+
+pub struct AdapterScreen {}
+
+impl AdapterScreen {
+    // @dev only adapters of the configured router owned by current account are listed
+    pub fn admin() {
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No current wallet set");
+                return;
+            }
+        };
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Checking adapter owners...".into());
+
+        let owned_adapters = AdapterAdmin::get_owned(
+            current_wallet.address,
+            Query::get_adapters(),
+            current_network.clone(),
+        );
+
+        sp.stop_with_newline();
+
+        let owned_adapters = match owned_adapters {
+            Ok(owned_adapters) if owned_adapters.is_empty() => {
+                println!("Current account doesn't own any router adapter");
+                return;
+            }
+            Ok(owned_adapters) => owned_adapters,
+            Err(err) => {
+                println!("Error while reading adapter owners: {}", err);
+                return;
+            }
+        };
+
+        let adapter_items: Vec<String> = owned_adapters
+            .iter()
+            .map(|owned| format!("{} \t gas estimate: {}", owned.adapter, owned.gas_estimate))
+            .collect();
+
+        let adapter_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Adapter")
+            .items(&adapter_items)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let owned = match adapter_selection {
+            Some(index) => &owned_adapters[index],
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let call = match Self::prompt_admin_call(owned, current_network.clone()) {
+            Some(call) => call,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        println!();
+        println!("Call: {} on {}", style(&call).yellow(), owned.adapter);
+
+        if let AdapterAdminCall::SetSwapGasEstimate(estimate) = &call {
+            println!(
+                "{}",
+                style(format!(
+                    "swapGasEstimate: {} -> {}",
+                    owned.gas_estimate, estimate
+                ))
+                .yellow()
+            );
+        }
+        println!();
+
+        let tx =
+            AdapterAdmin::build_admin_tx(&call, owned.adapter.address, current_network.clone());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Simulating...".into());
+
+        let simulation = Admin::simulate(
+            tx.to_owned(),
+            current_wallet.address,
+            current_network.clone(),
+        );
+
+        sp.stop_with_newline();
+
+        match simulation {
+            Ok(gas) => println!(
+                "{} gas estimate: {}",
+                style("Simulation succeeded,").green(),
+                gas
+            ),
+            Err(err) => {
+                println!("{} {}", style("Simulation failed:").red(), err);
+                return;
+            }
+        }
+
+        RouterScreen::send_admin_tx(
+            tx,
+            &current_wallet,
+            current_network,
+            "Yak adapter admin",
+            "safe-adapter-admin.json",
+        );
+    }
+
+    fn prompt_admin_call(
+        owned: &OwnedAdapter,
+        current_network: Arc<Network>,
+    ) -> Option<AdapterAdminCall> {
+        let actions = [
+            "setSwapGasEstimate (from measured swaps)",
+            "setAllowances",
+            "revokeAllowance",
+            "recoverERC20",
+            "recoverAVAX (native token)",
+            "transferOwnership",
+            "renounceOwnership",
+        ];
+
+        let action = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Adapter admin call")
+            .items(&actions)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap()?;
+
+        match action {
+            0 => Some(AdapterAdminCall::SetSwapGasEstimate(
+                Self::prompt_gas_estimate(owned, current_network),
+            )),
+            1 => Some(AdapterAdminCall::SetAllowances),
+            2 => Some(AdapterAdminCall::RevokeAllowance(
+                RouterScreen::prompt_address("Token"),
+                RouterScreen::prompt_address("Spender"),
+            )),
+            3 => {
+                let token = RouterScreen::prompt_address("Token to recover");
+
+                let balance =
+                    Token::get_token_balance(owned.adapter.address, token, current_network);
+
+                Some(AdapterAdminCall::RecoverERC20(
+                    token,
+                    RouterScreen::prompt_amount(balance),
+                ))
+            }
+            4 => {
+                let balance = Token::get_native_balance(owned.adapter.address, current_network);
+
+                Some(AdapterAdminCall::RecoverAVAX(RouterScreen::prompt_amount(
+                    balance,
+                )))
+            }
+            5 => {
+                let new_owner = RouterScreen::prompt_address("New owner");
+
+                let confirm = Confirm::new()
+                    .with_prompt(format!(
+                        "{} will be owned by {:?}, current account loses admin access. Continue?",
+                        owned.adapter.name, new_owner
+                    ))
+                    .default(false)
+                    .interact()
+                    .unwrap();
+
+                if confirm {
+                    Some(AdapterAdminCall::TransferOwnership(new_owner))
+                } else {
+                    None
+                }
+            }
+            6 => {
+                let confirm = Confirm::new()
+                    .with_prompt(format!(
+                        "{} will have no owner, gas estimate and allowances can't be changed anymore. Continue?",
+                        owned.adapter.name
+                    ))
+                    .default(false)
+                    .interact()
+                    .unwrap();
+
+                if confirm {
+                    Some(AdapterAdminCall::RenounceOwnership)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // @dev suggested estimate is median gas of recent single-hop swaps minus router overhead
+    fn prompt_gas_estimate(owned: &OwnedAdapter, current_network: Arc<Network>) -> U256 {
+        let blocks = Input::<u64>::new()
+            .with_prompt("Blocks to scan for adapter swaps")
+            .default(50000)
+            .interact_text()
+            .unwrap();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Measuring swap gas...".into());
+
+        let chain_id = current_network.chain_id;
+
+        let samples = AdapterAdmin::measure_gas(owned.adapter.address, blocks, current_network);
+
+        sp.stop_with_newline();
+
+        let mut suggested = owned.gas_estimate;
+
+        match samples {
+            Ok(samples) if samples.is_empty() => {
+                println!("No single-hop swaps through this adapter found");
+            }
+            Ok(samples) => {
+                let mut adapter_gas: Vec<U256> =
+                    samples.iter().map(|sample| sample.adapter_gas()).collect();
+
+                adapter_gas.sort();
+
+                let median = adapter_gas[adapter_gas.len() / 2];
+
+                suggested = median.saturating_sub(U256::from(AdapterAdmin::ROUTER_OVERHEAD_GAS));
+
+                println!(
+                    "Measured {} swaps: min {}, median {}, max {} (gas used without intrinsic gas)",
+                    adapter_gas.len(),
+                    adapter_gas[0],
+                    median,
+                    adapter_gas[adapter_gas.len() - 1]
+                );
+                println!(
+                    "Suggested: median minus router overhead of {} gas",
+                    AdapterAdmin::ROUTER_OVERHEAD_GAS
+                );
+            }
+            Err(err) => println!("Error while measuring swap gas: {}", err),
+        }
+
+        // @dev benchmark measures adapter swap alone, offered only when a stored run has this adapter
+        let benchmark_gas: Vec<U256> = Benchmark::get_runs(chain_id)
+            .into_iter()
+            .rev()
+            .find_map(|run| {
+                let gas: Vec<U256> = run
+                    .results
+                    .iter()
+                    .filter(|result| result.adapter == owned.adapter.address)
+                    .map(|result| result.measured_gas())
+                    .collect();
+
+                (!gas.is_empty()).then_some(gas)
+            })
+            .unwrap_or_default();
+
+        if !benchmark_gas.is_empty() {
+            let average = benchmark_gas
+                .iter()
+                .fold(U256::zero(), |sum, gas| sum.saturating_add(*gas))
+                / benchmark_gas.len();
+
+            println!(
+                "Latest benchmark: {} swaps, average adapter swap gas {}",
+                benchmark_gas.len(),
+                average
+            );
+
+            if Confirm::new()
+                .with_prompt("Suggest benchmark average instead?")
+                .default(false)
+                .interact()
+                .unwrap()
+            {
+                suggested = average;
+            }
+        }
+
+        println!("Current swapGasEstimate: {}", owned.gas_estimate);
+
+        let estimate = Input::<String>::new()
+            .with_prompt("New swapGasEstimate")
+            .default(suggested.to_string())
+            .validate_with(|input: &String| -> Result<(), &str> {
+                U256::from_dec_str(input)
+                    .map(|_| ())
+                    .map_err(|_| "Wrong gas estimate")
+            })
+            .interact_text()
+            .unwrap();
+
+        U256::from_dec_str(&estimate).unwrap()
+    }
+}
@@ -0,0 +1,197 @@
+use crate::address_book::{AddressBook, AddressBookEntry};
+use crate::network::Network;
+use crate::wallet::AccountWallet;
+use crate::Terminal;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+pub struct AddressBookScreen {}
+
+#[derive(FromPrimitive)]
+enum AddressBookTopics {
+    List,
+    Add,
+    Remove,
+    Import,
+    Export,
+    Back,
+}
+
+impl AddressBookScreen {
+    pub fn render() {
+        let topics = [
+            "1. List addresses",
+            "2. Add address",
+            "3. Remove address",
+            "4. Import from CSV/JSON",
+            "5. Export to CSV/JSON",
+            "<- Go back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(AddressBookTopics::List) => {
+                    Self::list_entries();
+                    Self::render();
+                }
+                Some(AddressBookTopics::Add) => {
+                    Self::add_entry();
+                    Terminal::render();
+                }
+                Some(AddressBookTopics::Remove) => {
+                    Self::remove_entry();
+                    Terminal::render();
+                }
+                Some(AddressBookTopics::Import) => {
+                    Self::import();
+                    Terminal::render();
+                }
+                Some(AddressBookTopics::Export) => {
+                    Self::export();
+                    Terminal::render();
+                }
+                Some(AddressBookTopics::Back) => {
+                    Terminal::render();
+                }
+                None => panic!("Error while selecting address book screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    // @dev used by recipient prompts, only entries for the current chain are shown
+    pub fn select_entry() -> Option<AddressBookEntry> {
+        let current_network = Network::get_current_network();
+
+        let entries = AddressBook::get_entries_for_chain(current_network.chain_id);
+
+        if entries.is_empty() {
+            println!("Address book is empty for {}", current_network.name);
+            return None;
+        }
+
+        let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+            .items(&entries)
+            .with_prompt("Address book")
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        selection.map(|index| entries[index].to_owned())
+    }
+
+    fn list_entries() {
+        let entries = AddressBook::get_entries();
+
+        if entries.is_empty() {
+            println!("Address book is empty");
+            return;
+        }
+
+        println!();
+        for entry in entries {
+            println!("{}", entry);
+        }
+        println!();
+    }
+
+    fn add_entry() {
+        let label: String = Input::new().with_prompt("Label").interact_text().unwrap();
+
+        let address: String = Input::new()
+            .with_prompt("Address")
+            .validate_with(|input: &String| -> Result<(), &str> {
+                AccountWallet::parse_address(input).map(|_| ())
+            })
+            .interact_text()
+            .unwrap();
+
+        let current_network = Network::get_current_network();
+
+        let chain_id = if Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Use this address only on {} (chain id: {})?",
+                current_network.name, current_network.chain_id
+            ))
+            .default(true)
+            .interact()
+            .unwrap()
+        {
+            Some(current_network.chain_id)
+        } else {
+            None
+        };
+
+        let is_saved = AddressBook::save_entry(AddressBookEntry {
+            label,
+            address: AccountWallet::parse_address(&address).unwrap(),
+            chain_id,
+        });
+
+        if is_saved {
+            println!("{}", style("Address successfully added!").green());
+        } else {
+            println!("Address already in address book");
+        }
+    }
+
+    fn remove_entry() {
+        let entries = AddressBook::get_entries();
+
+        if entries.is_empty() {
+            println!("Address book is empty");
+            return;
+        }
+
+        let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+            .items(&entries)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        if let Some(selected) = selection {
+            AddressBook::remove_entry(entries[selected].to_owned());
+
+            println!("{}", style("Address successfully removed!").green());
+        }
+    }
+
+    fn import() {
+        let path: String = Input::new()
+            .with_prompt("File to import (.csv or .json)")
+            .interact_text()
+            .unwrap();
+
+        match AddressBook::import(&path) {
+            Ok(imported) => println!(
+                "{}",
+                style(format!("Imported {} new addresses", imported)).green()
+            ),
+            Err(err) => println!("Error while importing address book: {}", err),
+        }
+    }
+
+    fn export() {
+        let path: String = Input::new()
+            .with_prompt("Export to file (.csv or .json)")
+            .default("address-book.json".to_owned())
+            .interact_text()
+            .unwrap();
+
+        match AddressBook::export(&path) {
+            Ok(exported) => println!(
+                "{}",
+                style(format!("Exported {} addresses to {}", exported, path)).green()
+            ),
+            Err(err) => println!("Error while exporting address book: {}", err),
+        }
+    }
+}
@@ -0,0 +1,429 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::address_book::AddressBook;
+use crate::allowance::{Allowance, AllowanceEntry};
+use crate::network::Network;
+use crate::offline::Offline;
+use crate::query::Query;
+use crate::safe::{Safe, SafeTransaction};
+use crate::token::{token_storage::TokenStorage, Token};
+use crate::wallet::storage::WalletStorage;
+use crate::Terminal;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Password, Select};
+use ethers::{
+    types::{H160, U256, U64},
+    utils::{format_units, parse_units},
+};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
+
+pub struct AllowanceScreen {}
+
+#[derive(FromPrimitive)]
+enum AllowanceTopics {
+    List,
+    Revoke,
+    Back,
+}
+
+impl AllowanceScreen {
+    pub fn render() {
+        let topics = [
+            "1. List allowances",
+            "2. Revoke or reduce allowances",
+            "<- Go back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(AllowanceTopics::List) => {
+                    Self::list_allowances();
+                    Self::render();
+                }
+                Some(AllowanceTopics::Revoke) => {
+                    Self::revoke_allowances();
+                    Terminal::render();
+                }
+                Some(AllowanceTopics::Back) => {
+                    Terminal::render();
+                }
+                None => panic!("Error while selecting allowance screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    fn list_allowances() {
+        if let Some((_, entries, spender_labels)) = Self::scan() {
+            println!();
+            for entry in &entries {
+                println!("{}", Self::format_entry(entry, &spender_labels));
+            }
+            println!();
+        }
+    }
+
+    fn revoke_allowances() {
+        let (current_wallet, entries, spender_labels) = match Self::scan() {
+            Some(scan) => scan,
+            None => return,
+        };
+
+        let items: Vec<String> = entries
+            .iter()
+            .map(|entry| Self::format_entry(entry, &spender_labels))
+            .collect();
+
+        let selection = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Allowances to change (space to select)")
+            .items(&items)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let selected: Vec<AllowanceEntry> = match selection {
+            Some(selected) if !selected.is_empty() => selected
+                .into_iter()
+                .map(|index| entries[index].to_owned())
+                .collect(),
+            _ => {
+                println!("Nothing selected");
+                return;
+            }
+        };
+
+        let actions = ["Revoke (set allowance to 0)", "Reduce to amount"];
+
+        let action = Select::with_theme(&ColorfulTheme::default())
+            .items(&actions)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let changes: Vec<(AllowanceEntry, U256)> = match action {
+            Some(0) => selected
+                .into_iter()
+                .map(|entry| (entry, U256::zero()))
+                .collect(),
+            Some(1) => selected
+                .into_iter()
+                .map(|entry| {
+                    let amount_input = Input::<String>::new()
+                        .with_prompt(format!(
+                            "New allowance of {} for {}",
+                            entry.symbol,
+                            Self::format_spender(entry.spender, &spender_labels)
+                        ))
+                        .validate_with(|input: &String| -> Result<(), &str> {
+                            match parse_units(input.to_owned(), entry.decimals) {
+                                Ok(amount) if amount < entry.amount => Ok(()),
+                                Ok(_) => Err("New allowance should be less than current"),
+                                Err(_) => Err("Wrong amount"),
+                            }
+                        })
+                        .interact_text()
+                        .unwrap();
+
+                    let amount = parse_units(amount_input, entry.decimals).unwrap();
+
+                    (entry, amount)
+                })
+                .collect(),
+            _ => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        // @dev watch-only Safe gets one Transaction Builder batch, other accounts unsigned transactions
+        if current_wallet.watch_only {
+            let mut sp = Spinner::new(Spinners::Aesthetic, "Checking for Safe...".into());
+
+            let safe_info = Safe::get_info(current_wallet.address, current_network.clone());
+
+            sp.stop_with_newline();
+
+            match safe_info {
+                Ok(_) => Self::export_safe_batch(current_wallet.address, &changes, current_network),
+                Err(_) => {
+                    println!(
+                        "{:?} is not a Safe, approvals are exported as unsigned transactions",
+                        current_wallet.address
+                    );
+                    Self::export_unsigned(current_wallet.address, &changes, current_network)
+                }
+            }
+            return;
+        }
+
+        let confirm = Confirm::new()
+            .with_prompt(format!("Send {} approve transactions?", changes.len()))
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let password: String = Password::new()
+            .with_prompt("Current Wallet password")
+            .interact()
+            .unwrap();
+
+        let wallet = crate::wallet::AccountWallet::decrypt_wallet(current_wallet.name, password);
+
+        if wallet.is_err() {
+            return;
+        }
+
+        let wallet = wallet.expect("Something wrong with wallet");
+
+        let signing_wallet = wallet.wallet();
+
+        let mut failed: Vec<String> = vec![];
+
+        for (entry, amount) in &changes {
+            let mut sp = Spinner::new(
+                Spinners::Aesthetic,
+                format!("Approving {} to {}...", entry.symbol, entry.spender),
+            );
+
+            let receipt = Token::approve(
+                entry.spender,
+                *amount,
+                entry.token,
+                signing_wallet,
+                current_network.clone(),
+            );
+
+            sp.stop_with_newline();
+
+            match receipt {
+                Some(receipt) => {
+                    println!(
+                        "tx url: {explorer}/tx/{:?}",
+                        receipt.transaction_hash,
+                        explorer = current_network.explorer_url
+                    );
+
+                    if receipt.status == Some(U64::zero()) {
+                        failed.push(Self::format_entry(entry, &spender_labels));
+                    }
+                }
+                None => {
+                    println!("Error when getting tx hash on approve");
+                    failed.push(Self::format_entry(entry, &spender_labels));
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            println!("{}", style("Allowances updated!").green());
+            return;
+        }
+
+        println!(
+            "{}",
+            style(format!(
+                "{} of {} allowances updated, failed:",
+                changes.len() - failed.len(),
+                changes.len()
+            ))
+            .red()
+        );
+
+        for entry in failed {
+            println!("  {}", entry);
+        }
+    }
+
+    // @dev router and its adapters are the spenders this CLI approves, address book entries are checked too
+    fn scan() -> Option<(WalletStorage, Vec<AllowanceEntry>, HashMap<H160, String>)> {
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No current wallet set");
+                return None;
+            }
+        };
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let blocks = Input::<u64>::new()
+            .with_prompt("Blocks to scan for Approval events")
+            .default(100000)
+            .interact_text()
+            .unwrap();
+
+        let mut spender_labels: HashMap<H160, String> = HashMap::new();
+
+        if let Some(yak_router) = &current_network.yak_router {
+            spender_labels.insert(yak_router.parse::<H160>().unwrap(), "Yak Router".to_owned());
+        }
+
+        for adapter in Query::get_adapters() {
+            spender_labels.insert(adapter.address, adapter.name);
+        }
+
+        for entry in AddressBook::get_entries_for_chain(current_network.chain_id) {
+            spender_labels.entry(entry.address).or_insert(entry.label);
+        }
+
+        let known_tokens: Vec<Token> = TokenStorage::get_local_tokens()
+            .into_iter()
+            .filter(|token| token.chain_id == Some(current_network.chain_id))
+            .collect();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Scanning allowances...".into());
+
+        let entries = Allowance::scan(
+            current_wallet.address,
+            blocks,
+            spender_labels.keys().cloned().collect(),
+            known_tokens,
+            current_network,
+        );
+
+        sp.stop_with_newline();
+
+        match entries {
+            Ok(entries) if entries.is_empty() => {
+                println!("No outstanding allowances found");
+                None
+            }
+            Ok(entries) => Some((current_wallet, entries, spender_labels)),
+            Err(err) => {
+                println!("Error while scanning allowances: {}", err);
+                None
+            }
+        }
+    }
+
+    fn format_spender(spender: H160, spender_labels: &HashMap<H160, String>) -> String {
+        match spender_labels.get(&spender) {
+            Some(label) => format!("{} ({:?})", label, spender),
+            None => {
+                let current_network = Network::get_current_network();
+
+                AddressBook::format_address(spender, current_network.chain_id)
+            }
+        }
+    }
+
+    fn format_entry(entry: &AllowanceEntry, spender_labels: &HashMap<H160, String>) -> String {
+        let amount = if entry.is_unlimited() {
+            style("unlimited".to_owned()).red().to_string()
+        } else {
+            format_units(entry.amount, entry.decimals).unwrap()
+        };
+
+        format!(
+            "{} \t {} \t -> {}",
+            entry.symbol,
+            amount,
+            Self::format_spender(entry.spender, spender_labels)
+        )
+    }
+
+    // @dev one file per approve, nonces follow each other so files are sent in order
+    fn export_unsigned(
+        from: H160,
+        changes: &[(AllowanceEntry, U256)],
+        current_network: Arc<Network>,
+    ) {
+        let path = Input::<String>::new()
+            .with_prompt("Export unsigned transactions to files with prefix")
+            .default("unsigned-allowance".to_owned())
+            .interact_text()
+            .unwrap();
+
+        let mut nonce: Option<U256> = None;
+
+        for (i, (entry, amount)) in changes.iter().enumerate() {
+            let tx = Token::build_approve_tx(
+                entry.spender,
+                *amount,
+                entry.token,
+                current_network.clone(),
+            );
+
+            let unsigned_tx = match Offline::populate(tx, from, current_network.clone()) {
+                Ok(mut unsigned_tx) => {
+                    if let Some(nonce) = nonce {
+                        unsigned_tx.nonce = nonce + i;
+                    } else {
+                        nonce = Some(unsigned_tx.nonce);
+                    }
+
+                    unsigned_tx
+                }
+                Err(err) => {
+                    println!("Error while populating {} approve: {}", entry.symbol, err);
+                    return;
+                }
+            };
+
+            let file = format!("{}-{}.json", path, i + 1);
+
+            match Offline::export_unsigned(&unsigned_tx, &file) {
+                Ok(_) => println!(
+                    "{} {} (nonce {})",
+                    style("Unsigned transaction exported to").green(),
+                    file,
+                    unsigned_tx.nonce
+                ),
+                Err(err) => {
+                    println!("Error while exporting transaction: {}", err);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn export_safe_batch(
+        safe: H160,
+        changes: &[(AllowanceEntry, U256)],
+        current_network: Arc<Network>,
+    ) {
+        let transactions: Vec<SafeTransaction> = changes
+            .iter()
+            .map(|(entry, amount)| {
+                SafeTransaction::from(Token::build_approve_tx(
+                    entry.spender,
+                    *amount,
+                    entry.token,
+                    current_network.clone(),
+                ))
+            })
+            .collect();
+
+        let batch = Safe::build_batch(
+            safe,
+            current_network.chain_id,
+            "Revoke or reduce allowances",
+            &transactions,
+        );
+
+        let path = Input::<String>::new()
+            .with_prompt("Watch-only account, export Safe batch to file")
+            .default("safe-allowances.json".to_owned())
+            .interact_text()
+            .unwrap();
+
+        match Safe::export_batch(&batch, &path) {
+            Ok(_) => println!("{} {}", style("Safe batch exported to").green(), path),
+            Err(err) => println!("Error while exporting batch: {}", err),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use crate::benchmark::{Benchmark, BenchmarkRun};
+use crate::network::Network;
+use crate::query::Query;
+use crate::Terminal;
+use console::style;
+use dialoguer::Input;
+use ethers::{
+    types::{H160, U256},
+    utils::{format_units, parse_units},
+};
+use spinners::{Spinner, Spinners};
+
+pub struct BenchmarkScreen {}
+
+impl BenchmarkScreen {
+    pub fn render() {
+        let current_network = Network::get_current_network();
+
+        let amounts_input = Input::<String>::new()
+            .with_prompt(format!(
+                "Amounts in {} (comma separated)",
+                current_network.currency_symbol
+            ))
+            .default("1,100".to_owned())
+            .validate_with(|input: &String| -> Result<(), &str> {
+                Self::parse_amounts(input, current_network.currency_decimals)
+                    .map(|_| ())
+                    .ok_or("Wrong amount in list")
+            })
+            .interact_text()
+            .unwrap();
+
+        let amounts =
+            Self::parse_amounts(&amounts_input, current_network.currency_decimals).unwrap();
+
+        if let Err(err) = Self::run(amounts) {
+            println!("{}", err);
+        }
+    }
+
+    // @dev `benchmark [--amount <amounts, comma separated>] [--history]`, exits with code 1 on errors
+    pub fn command(args: &[String]) {
+        let current_network = Network::get_current_network();
+
+        let mut amounts = Self::parse_amounts("1,100", current_network.currency_decimals);
+        let mut is_history = false;
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--amount" => {
+                    amounts = args.next().and_then(|amounts| {
+                        Self::parse_amounts(amounts, current_network.currency_decimals)
+                    })
+                }
+                "--history" => is_history = true,
+                _ => {
+                    amounts = None;
+                    break;
+                }
+            }
+        }
+
+        let amounts = match amounts {
+            Some(amounts) => amounts,
+            None => Terminal::exit_with_error(
+                "Usage: benchmark [--amount <amounts, comma separated>] [--history]",
+            ),
+        };
+
+        if is_history {
+            Self::history();
+        } else if let Err(err) = Self::run(amounts) {
+            Terminal::exit_with_error(&err);
+        }
+    }
+
+    // @dev each run is compared with on-chain swapGasEstimate and previous stored run
+    fn run(amounts: Vec<U256>) -> Result<(), String> {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let previous = Benchmark::get_runs(current_network.chain_id).pop();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Benchmarking adapters...".into());
+
+        let run = Benchmark::run(Query::get_adapters(), amounts, current_network.clone());
+
+        sp.stop_with_newline();
+
+        let run = match run {
+            Ok(run) if run.results.is_empty() => {
+                Self::print_failures(&run.failures);
+                return Err("No adapter swap could be simulated".to_owned());
+            }
+            Ok(run) => run,
+            Err(err) => return Err(format!("Error while benchmarking adapters: {}", err)),
+        };
+
+        Self::print_run(&run, previous.as_ref(), &current_network);
+
+        Self::print_failures(&run.failures);
+
+        Benchmark::save_run(current_network.chain_id, &run);
+
+        println!(
+            "{}",
+            style(format!(
+                "Benchmark at block {} saved, measured gas is adapter swap without intrinsic gas",
+                run.block_number
+            ))
+            .green()
+        );
+
+        Ok(())
+    }
+
+    fn print_run(run: &BenchmarkRun, previous: Option<&BenchmarkRun>, current_network: &Network) {
+        println!();
+        println!(
+            "{:<32} {:<22} {:>10} {:>10} {:>9} {:>10}",
+            "Adapter", "Pair", "Measured", "Estimate", "Diff", "Previous"
+        );
+
+        for result in &run.results {
+            let pair = format!(
+                "{} {}->{}",
+                format_units(result.amount_in, current_network.currency_decimals as u32)
+                    .unwrap()
+                    .trim_end_matches('0')
+                    .trim_end_matches('.'),
+                current_network.currency_symbol,
+                result.token_out_symbol
+            );
+
+            let previous_gas = previous
+                .and_then(|previous| {
+                    previous
+                        .results
+                        .iter()
+                        .find(|previous_result| previous_result.is_same_case(result))
+                })
+                .map(|previous_result| previous_result.measured_gas().to_string())
+                .unwrap_or_else(|| "-".to_owned());
+
+            let estimate_diff = result.estimate_diff();
+
+            let diff = format!("{:>+8.1}%", estimate_diff);
+            let diff = match estimate_diff.abs() {
+                abs if abs >= 25.0 => style(diff).red(),
+                abs if abs >= 10.0 => style(diff).yellow(),
+                _ => style(diff).green(),
+            };
+
+            println!(
+                "{:<32} {:<22} {:>10} {:>10} {} {:>10}",
+                result.name,
+                pair,
+                result.measured_gas(),
+                result.swap_gas_estimate,
+                diff,
+                previous_gas
+            );
+        }
+        println!();
+    }
+
+    fn print_failures(failures: &[String]) {
+        if failures.is_empty() {
+            return;
+        }
+
+        println!(
+            "{}",
+            style(format!(
+                "{} case(s) could not be simulated:",
+                failures.len()
+            ))
+            .yellow()
+        );
+
+        for failure in failures {
+            println!("  {}", failure);
+        }
+        println!();
+    }
+
+    // @dev average measured gas per adapter for every stored run
+    fn history() {
+        let current_network = Network::get_current_network();
+
+        let runs = Benchmark::get_runs(current_network.chain_id);
+
+        if runs.is_empty() {
+            println!("No benchmark runs stored for {}", current_network.name);
+            return;
+        }
+
+        let mut adapters: Vec<(H160, String)> = vec![];
+
+        for run in &runs {
+            for result in &run.results {
+                if !adapters
+                    .iter()
+                    .any(|(address, _)| *address == result.adapter)
+                {
+                    adapters.push((result.adapter, result.name.to_owned()));
+                }
+            }
+        }
+
+        for (address, name) in adapters {
+            println!();
+            println!("{} {:?}", style(name).yellow(), address);
+            println!(
+                "{:<12} {:<10} {:>12} {:>10} {:>9}",
+                "Block", "Swaps", "Avg measured", "Estimate", "Diff"
+            );
+
+            for run in &runs {
+                let results: Vec<_> = run
+                    .results
+                    .iter()
+                    .filter(|result| result.adapter == address)
+                    .collect();
+
+                if results.is_empty() {
+                    continue;
+                }
+
+                let avg_measured = results
+                    .iter()
+                    .fold(U256::zero(), |sum, result| sum + result.measured_gas())
+                    / results.len();
+
+                let avg_diff = results
+                    .iter()
+                    .map(|result| result.estimate_diff())
+                    .sum::<f64>()
+                    / results.len() as f64;
+
+                println!(
+                    "{:<12} {:<10} {:>12} {:>10} {:>+8.1}%",
+                    run.block_number,
+                    results.len(),
+                    avg_measured,
+                    results[0].swap_gas_estimate,
+                    avg_diff
+                );
+            }
+        }
+        println!();
+    }
+
+    fn parse_amounts(input: &str, decimals: u8) -> Option<Vec<U256>> {
+        input
+            .split(',')
+            .map(|amount| {
+                parse_units(amount.trim(), decimals as u32)
+                    .ok()
+                    .filter(|amount| !amount.is_zero())
+            })
+            .collect()
+    }
+}
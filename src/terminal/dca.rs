@@ -0,0 +1,848 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::db::{reload_db, DB};
+use crate::dca::{Dca, DcaLimits, DcaPlan, DcaRun, DcaRunStatus, DcaSchedule, DcaStatus};
+use crate::network::Network;
+use crate::order::LimitOrders;
+use crate::query::Query;
+use crate::settings::Settings;
+use crate::swap::Swap;
+use crate::token::Token;
+use crate::wallet::storage::WalletStorage;
+use crate::wallet::AccountWallet;
+use crate::Terminal;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
+use ethers::{
+    types::{H160, U256, U64},
+    utils::{format_units, parse_units},
+};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
+
+use super::order::LimitOrderScreen;
+use super::query::{QueryPrompt, QueryScreen};
+use super::swap::SwapScreen;
+use super::watch::WatchScreen;
+
+pub struct DcaScreen {}
+
+#[derive(FromPrimitive)]
+enum DcaTopics {
+    Create,
+    List,
+    History,
+    Cancel,
+    Run,
+    Back,
+}
+
+impl DcaScreen {
+    // @dev due plans are checked this often, intervals are hours or longer
+    const TICK: u64 = 10;
+
+    pub fn render() {
+        let topics = [
+            "1. Create DCA plan",
+            "2. List plans",
+            "3. Plan history",
+            "4. Cancel plan",
+            "5. Run scheduler",
+            "<- Go back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(DcaTopics::Create) => {
+                    Self::create();
+                    Self::render();
+                }
+                Some(DcaTopics::List) => {
+                    Self::list();
+                    Self::render();
+                }
+                Some(DcaTopics::History) => {
+                    if let Some(plan) = Self::select_plan(false) {
+                        if let Err(err) = Self::history(plan.id) {
+                            println!("{}", err);
+                        }
+                    }
+                    Self::render();
+                }
+                Some(DcaTopics::Cancel) => {
+                    if let Some(plan) = Self::select_plan(true) {
+                        if let Err(err) = Self::cancel_plan(plan.id) {
+                            println!("{}", err);
+                        }
+                    }
+                    Self::render();
+                }
+                Some(DcaTopics::Run) => {
+                    let sign_items = [
+                        "Unlock accounts once for this session",
+                        "Ask password on every run",
+                    ];
+
+                    let sign_selection = Select::with_theme(&ColorfulTheme::default())
+                        .with_prompt("Signing")
+                        .items(&sign_items)
+                        .default(0)
+                        .interact_on_opt(&Term::stderr())
+                        .unwrap();
+
+                    if let Some(sign_selection) = sign_selection {
+                        if let Err(err) = Self::run(sign_selection == 1) {
+                            println!("{}", err);
+                        }
+                    }
+                    Self::render();
+                }
+                Some(DcaTopics::Back) => {
+                    Terminal::render();
+                }
+                None => panic!("Error while selecting DCA screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    // @dev `dca [list | history <id> | cancel <id> | run [--sign-each-run]]`, exits with code 1 on errors
+    pub fn command(args: &[String]) {
+        let usage = "Usage: dca [list | history <id> | cancel <id> | run [--sign-each-run]]";
+
+        let result = match args {
+            [] => {
+                Self::list();
+                Ok(())
+            }
+            [command] if command == "list" => {
+                Self::list();
+                Ok(())
+            }
+            [command, id] if command == "history" || command == "cancel" => {
+                match id.parse::<u32>() {
+                    Ok(id) if command == "history" => Self::history(id),
+                    Ok(id) => Self::cancel_plan(id),
+                    Err(_) => Err(usage.to_owned()),
+                }
+            }
+            [command] if command == "run" => Self::run(false),
+            [command, flag] if command == "run" && flag == "--sign-each-run" => Self::run(true),
+            _ => Err(usage.to_owned()),
+        };
+
+        if let Err(err) = result {
+            Terminal::exit_with_error(&err);
+        }
+    }
+
+    fn create() {
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No wallet set");
+                return;
+            }
+        };
+
+        if current_wallet.watch_only {
+            println!(
+                "{}",
+                style("Current account is watch-only, scheduler can't sign runs for it").red()
+            );
+            return;
+        }
+
+        println!("Amount in is spent on every run");
+
+        let prompt_query = QueryScreen::prompt_query();
+
+        let interval_items = ["Hourly", "Daily", "Weekly", "Custom (hours)"];
+
+        let interval_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Interval")
+            .items(&interval_items)
+            .default(1)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let interval = match interval_selection {
+            Some(0) => 3600,
+            Some(1) => 86400,
+            Some(2) => 604800,
+            Some(_) => {
+                Input::<u64>::new()
+                    .with_prompt("Interval in hours")
+                    .validate_with(|hours: &u64| -> Result<(), &str> {
+                        match hours {
+                            0 => Err("Interval should be at least 1 hour"),
+                            _ => Ok(()),
+                        }
+                    })
+                    .interact_text()
+                    .unwrap()
+                    * 3600
+            }
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let max_runs = Input::<u32>::new()
+            .with_prompt("Number of runs (0 for no limit)")
+            .default(10)
+            .interact_text()
+            .unwrap();
+
+        let end_days = Input::<u64>::new()
+            .with_prompt("End in days (0 for no end date)")
+            .default(0)
+            .interact_text()
+            .unwrap();
+
+        if max_runs == 0 && end_days == 0 {
+            println!("Plan needs number of runs or end date");
+            return;
+        }
+
+        let max_slippage = Input::<f64>::new()
+            .with_prompt("Max slippage and price impact in %")
+            .default(Settings::get_slippage() as f64 / 10.0)
+            .validate_with(|slippage: &f64| -> Result<(), &str> {
+                match slippage {
+                    slippage if *slippage >= 0.1 && *slippage <= 50.0 => Ok(()),
+                    _ => Err("Slippage should be between 0.1% and 50%"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Estimating gas cost...".into());
+
+        let current_gas_cost = Query::find_best_path_with_gas(
+            prompt_query.amount_in,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+            prompt_query.max_steps,
+        )
+        .ok()
+        .filter(|offer| !offer.adapters.is_empty())
+        .zip(Query::get_gas_price().ok())
+        .map(|(offer, gas_price)| offer.gas_estimate * gas_price);
+
+        sp.stop_with_newline();
+
+        let native_decimals = current_network.currency_decimals as u32;
+
+        let mut max_gas_input = Input::<String>::new();
+
+        max_gas_input.with_prompt(format!(
+            "Max gas cost per run in {}",
+            current_network.currency_symbol
+        ));
+
+        // @dev router estimate doesn't include tx overhead, so double of it is suggested
+        if let Some(current_gas_cost) = current_gas_cost {
+            println!(
+                "Current swap gas cost: {} {}",
+                format_units(current_gas_cost, native_decimals).unwrap(),
+                current_network.currency_symbol
+            );
+
+            max_gas_input.default(format_units(current_gas_cost * 2, native_decimals).unwrap());
+        }
+
+        let max_gas_input = max_gas_input
+            .validate_with(|input: &String| -> Result<(), &str> {
+                parse_units(input, native_decimals)
+                    .map(|_| ())
+                    .map_err(|_| "Wrong amount")
+            })
+            .interact_text()
+            .unwrap();
+
+        let max_gas_cost = parse_units(max_gas_input, native_decimals).unwrap();
+
+        let schedule = DcaSchedule {
+            interval,
+            max_runs: Some(max_runs).filter(|max_runs| *max_runs > 0),
+            end_at: Some(end_days)
+                .filter(|end_days| *end_days > 0)
+                .map(|end_days| LimitOrders::now() + end_days * 86400),
+        };
+
+        let limits = DcaLimits {
+            max_slippage: (max_slippage * 10.0).round() as u32,
+            max_gas_cost,
+        };
+
+        let confirm = Confirm::new()
+            .with_prompt(format!(
+                "Swap {} {} to {} every {}, first run now?",
+                format_units(prompt_query.amount_in, prompt_query.token_in.decimals).unwrap(),
+                prompt_query.token_in.symbol,
+                prompt_query.token_out.symbol,
+                Self::format_duration(interval)
+            ))
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let plan = Dca::create(
+            current_network.chain_id,
+            current_wallet.address,
+            prompt_query.token_in.to_owned(),
+            prompt_query.token_out.to_owned(),
+            prompt_query.amount_in,
+            schedule,
+            limits,
+        );
+
+        // @dev allowance for all planned runs, runs are simulated so missing allowance only fails them
+        let token_in = prompt_query.token_in.address.parse::<H160>().unwrap();
+
+        if !Token::is_native(token_in) {
+            let yak_router_address = current_network
+                .yak_router
+                .as_ref()
+                .unwrap()
+                .parse::<H160>()
+                .unwrap();
+
+            let allowance = Token::get_allowance(
+                current_wallet.address,
+                yak_router_address,
+                token_in,
+                current_network.clone(),
+            );
+
+            let total_amount = prompt_query.amount_in
+                * U256::from(plan.remaining_runs(LimitOrders::now()).unwrap_or(1));
+
+            if allowance < total_amount {
+                let approve = Confirm::new()
+                    .with_prompt("Router allowance is not enough for all runs, approve now?")
+                    .default(true)
+                    .interact()
+                    .unwrap();
+
+                if approve {
+                    let password: String = Password::new()
+                        .with_prompt("Current Wallet password")
+                        .interact()
+                        .unwrap();
+
+                    if let Ok(wallet) =
+                        AccountWallet::decrypt_wallet(current_wallet.name.to_owned(), password)
+                    {
+                        let total_query = QueryPrompt {
+                            amount_in: total_amount,
+                            ..prompt_query
+                        };
+
+                        SwapScreen::approve_router(
+                            &total_query,
+                            allowance,
+                            yak_router_address,
+                            wallet.wallet(),
+                            current_network.clone(),
+                        );
+                    }
+                } else {
+                    println!("Runs will fail until router is approved");
+                }
+            }
+        }
+
+        println!(
+            "{} #{}, start scheduler to run it",
+            style("DCA plan created").green(),
+            plan.id
+        );
+    }
+
+    fn list() {
+        let current_network = Network::get_current_network();
+
+        let plans = Dca::get_plans(current_network.chain_id);
+
+        if plans.is_empty() {
+            println!("No DCA plans for {}", current_network.name);
+            return;
+        }
+
+        let now = LimitOrders::now();
+
+        println!();
+        for plan in &plans {
+            let status = match plan.status {
+                DcaStatus::Active => style(format!(
+                    "active, next run {}",
+                    match plan.next_run_at.saturating_sub(now) {
+                        0 => "due".to_owned(),
+                        seconds => format!("in {}", Self::format_duration(seconds)),
+                    }
+                )),
+                DcaStatus::Completed => style(plan.status.to_string()).green(),
+                DcaStatus::Cancelled => style(plan.status.to_string()).dim(),
+            };
+
+            let runs = match plan.schedule.max_runs {
+                Some(max_runs) => format!("{}/{} filled", plan.filled_runs(), max_runs),
+                None => format!("{} filled", plan.filled_runs()),
+            };
+
+            let skipped = plan
+                .runs
+                .iter()
+                .filter(|run| run.status != DcaRunStatus::Filled)
+                .count();
+
+            println!(
+                "{}, {}, {} skipped or failed [{}]",
+                Self::format_plan(plan),
+                runs,
+                skipped,
+                status
+            );
+        }
+        println!();
+    }
+
+    fn history(id: u32) -> Result<(), String> {
+        let current_network = Network::get_current_network();
+
+        let plan = Dca::get_plans(current_network.chain_id)
+            .into_iter()
+            .find(|plan| plan.id == id)
+            .ok_or("Plan not found")?;
+
+        println!();
+        println!("{}", Self::format_plan(&plan));
+
+        if plan.runs.is_empty() {
+            println!("No runs yet");
+            println!();
+            return Ok(());
+        }
+
+        let now = LimitOrders::now();
+
+        for run in &plan.runs {
+            let status = match run.status {
+                DcaRunStatus::Filled => style(run.status.to_string()).green(),
+                DcaRunStatus::Skipped => style(run.status.to_string()).yellow(),
+                DcaRunStatus::Failed => style(run.status.to_string()).red(),
+            };
+
+            let amount_out = run
+                .amount_out
+                .map(|amount_out| {
+                    format!(
+                        "{} {}",
+                        format_units(amount_out, plan.token_out.decimals).unwrap(),
+                        plan.token_out.symbol
+                    )
+                })
+                .unwrap_or_else(|| "-".to_owned());
+
+            let price_impact = run
+                .price_impact
+                .map(|price_impact| format!("{:.1}%", price_impact as f64 / 10.0))
+                .unwrap_or_else(|| "-".to_owned());
+
+            let gas_cost = run
+                .gas_cost
+                .map(|gas_cost| {
+                    format!(
+                        "{} {}",
+                        format_units(gas_cost, current_network.currency_decimals as u32).unwrap(),
+                        current_network.currency_symbol
+                    )
+                })
+                .unwrap_or_else(|| "-".to_owned());
+
+            println!(
+                "{:>10} ago  {:<8} quoted {}  impact {}  gas {}",
+                Self::format_duration(now.saturating_sub(run.timestamp)),
+                status,
+                amount_out,
+                price_impact,
+                gas_cost
+            );
+
+            if let Some(tx_hash) = run.tx_hash {
+                println!(
+                    "            tx url: {explorer}/tx/{:?}",
+                    tx_hash,
+                    explorer = current_network.explorer_url
+                );
+            }
+
+            if let Some(reason) = &run.reason {
+                println!("            {}", reason);
+            }
+        }
+        println!();
+
+        Ok(())
+    }
+
+    fn select_plan(is_active_only: bool) -> Option<DcaPlan> {
+        let current_network = Network::get_current_network();
+
+        let plans: Vec<DcaPlan> = Dca::get_plans(current_network.chain_id)
+            .into_iter()
+            .filter(|plan| !is_active_only || plan.status == DcaStatus::Active)
+            .collect();
+
+        if plans.is_empty() {
+            println!("No DCA plans");
+            return None;
+        }
+
+        let plan_items: Vec<String> = plans.iter().map(Self::format_plan).collect();
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Plan")
+            .items(&plan_items)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap()?;
+
+        plans.into_iter().nth(selection)
+    }
+
+    fn cancel_plan(id: u32) -> Result<(), String> {
+        let current_network = Network::get_current_network();
+
+        let plan = Dca::cancel(current_network.chain_id, id)?;
+
+        println!("{} #{}", style("Cancelled plan").green(), plan.id);
+
+        Ok(())
+    }
+
+    // @dev per-run signing prompts for password, so Enter can't be used to stop then
+    fn run(is_sign_each_run: bool) -> Result<(), String> {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let active_plans: Vec<DcaPlan> = Dca::get_plans(current_network.chain_id)
+            .into_iter()
+            .filter(|plan| plan.status == DcaStatus::Active)
+            .collect();
+
+        if active_plans.is_empty() {
+            println!("No active DCA plans");
+            return Ok(());
+        }
+
+        let signers = if is_sign_each_run {
+            HashMap::new()
+        } else {
+            let accounts: Vec<H160> = active_plans.iter().map(|plan| plan.account).collect();
+
+            let signers = LimitOrderScreen::unlock_accounts(&accounts);
+
+            if signers.is_empty() {
+                return Err("No plan account unlocked, scheduler not started".to_owned());
+            }
+
+            signers
+        };
+
+        let is_stopped = if is_sign_each_run {
+            println!(
+                "Running {} active plan(s) (press Ctrl+C to stop)",
+                active_plans.len()
+            );
+
+            Arc::new(AtomicBool::new(false))
+        } else {
+            println!(
+                "Running {} active plan(s) (press Enter to stop)",
+                active_plans.len()
+            );
+
+            WatchScreen::listen_for_stop()
+        };
+
+        while !is_stopped.load(Ordering::Relaxed) {
+            let now = LimitOrders::now();
+
+            reload_db(&mut DB.lock().unwrap());
+
+            let due_plans = Dca::get_plans(current_network.chain_id)
+                .into_iter()
+                .filter(|plan| plan.is_due(now))
+                .filter(|plan| is_sign_each_run || signers.contains_key(&plan.account));
+
+            for mut plan in due_plans {
+                if is_stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                Self::run_plan(&mut plan, now, &signers, current_network.clone());
+
+                if !Dca::save_active_plan(current_network.chain_id, &plan) {
+                    println!(
+                        "{} #{}, scheduler changes are not saved",
+                        style("Plan is no longer active").yellow(),
+                        plan.id
+                    );
+
+                    if let Some(tx_hash) = plan.runs.last().and_then(|run| run.tx_hash) {
+                        println!(
+                            "Run was sent, tx url: {explorer}/tx/{:?}",
+                            tx_hash,
+                            explorer = current_network.explorer_url
+                        );
+                    }
+                }
+            }
+
+            let mut waited = 0;
+
+            while waited < Self::TICK && !is_stopped.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_secs(1));
+                waited += 1;
+            }
+        }
+
+        println!("DCA scheduler stopped");
+
+        Ok(())
+    }
+
+    // @dev signer from session, otherwise account is unlocked for this run only
+    fn run_plan(
+        plan: &mut DcaPlan,
+        now: u64,
+        signers: &HashMap<H160, AccountWallet>,
+        current_network: Arc<Network>,
+    ) {
+        if plan.is_finished(now) {
+            plan.status = DcaStatus::Completed;
+            println!("{} #{}", style("DCA plan completed").green(), plan.id);
+            return;
+        }
+
+        let run = Self::execute_run(plan, now, signers, current_network.clone());
+
+        let status = match run.status {
+            DcaRunStatus::Filled => style(format!("Plan #{} run filled", plan.id)).green(),
+            DcaRunStatus::Skipped => style(format!("Plan #{} run skipped", plan.id)).yellow(),
+            DcaRunStatus::Failed => style(format!("Plan #{} run failed", plan.id)).red(),
+        };
+
+        match &run.reason {
+            Some(reason) => println!("{}: {}", status, reason),
+            None => println!("{}", status),
+        }
+
+        if let Some(tx_hash) = run.tx_hash {
+            println!(
+                "tx url: {explorer}/tx/{:?}",
+                tx_hash,
+                explorer = current_network.explorer_url
+            );
+        }
+
+        plan.runs.push(run);
+        plan.schedule_next_run(now);
+
+        if plan.is_finished(plan.next_run_at) {
+            plan.status = DcaStatus::Completed;
+            println!("{} #{}", style("DCA plan completed").green(), plan.id);
+        }
+    }
+
+    fn execute_run(
+        plan: &DcaPlan,
+        now: u64,
+        signers: &HashMap<H160, AccountWallet>,
+        current_network: Arc<Network>,
+    ) -> DcaRun {
+        let mut run = DcaRun {
+            timestamp: now,
+            status: DcaRunStatus::Skipped,
+            amount_out: None,
+            price_impact: None,
+            gas_cost: None,
+            tx_hash: None,
+            reason: None,
+        };
+
+        let token_in = plan.token_in.address.parse::<H160>().unwrap();
+        let token_out = plan.token_out.address.parse::<H160>().unwrap();
+        let max_steps = Settings::get_max_steps();
+
+        let offer = match Query::find_best_path_with_gas(
+            plan.amount_per_run,
+            token_in,
+            token_out,
+            max_steps,
+        ) {
+            Ok(offer) if !offer.adapters.is_empty() => offer,
+            Ok(_) => {
+                run.reason = Some("Yak path not found".to_owned());
+                return run;
+            }
+            Err(err) => {
+                run.reason = Some(format!("Error while getting best path: {}", err));
+                return run;
+            }
+        };
+
+        let amount_out = *offer.amounts.last().unwrap();
+
+        run.amount_out = Some(amount_out);
+
+        let reference_in = match plan.amount_per_run / Dca::REFERENCE_DIVISOR {
+            reference_in if reference_in.is_zero() => plan.amount_per_run,
+            reference_in => reference_in,
+        };
+
+        let reference_out =
+            Query::find_best_path_with_gas(reference_in, token_in, token_out, max_steps)
+                .ok()
+                .and_then(|reference| reference.amounts.last().copied())
+                .unwrap_or_default();
+
+        let price_impact =
+            Dca::price_impact(plan.amount_per_run, amount_out, reference_in, reference_out);
+
+        run.price_impact = Some(price_impact);
+
+        if price_impact > plan.limits.max_slippage {
+            run.reason = Some(format!(
+                "price impact {:.1}% above limit {:.1}%",
+                price_impact as f64 / 10.0,
+                plan.limits.max_slippage as f64 / 10.0
+            ));
+            return run;
+        }
+
+        let tx = plan.build_run_tx(offer, current_network.clone());
+
+        let gas_cost = match Swap::estimate_tx_cost(tx.to_owned(), current_network.clone()) {
+            Ok(gas_cost) => gas_cost,
+            Err(err) => {
+                run.status = DcaRunStatus::Failed;
+                run.reason = Some(format!("simulation failed: {}", err));
+                return run;
+            }
+        };
+
+        run.gas_cost = Some(gas_cost);
+
+        if gas_cost > plan.limits.max_gas_cost {
+            run.reason = Some(format!(
+                "gas cost {} above limit {} {}",
+                format_units(gas_cost, current_network.currency_decimals as u32).unwrap(),
+                format_units(
+                    plan.limits.max_gas_cost,
+                    current_network.currency_decimals as u32
+                )
+                .unwrap(),
+                current_network.currency_symbol
+            ));
+            return run;
+        }
+
+        let run_signer;
+
+        let signer = match signers.get(&plan.account) {
+            Some(signer) => signer,
+            None => {
+                println!("Plan #{} is due: {}", plan.id, Self::format_plan(plan));
+
+                run_signer = LimitOrderScreen::unlock_account(plan.account);
+
+                match &run_signer {
+                    Some(signer) => signer,
+                    None => {
+                        run.reason = Some("account not unlocked".to_owned());
+                        return run;
+                    }
+                }
+            }
+        };
+
+        // @dev plan may be cancelled in another session since this tick started
+        match Dca::get_plan(current_network.chain_id, plan.id) {
+            Some(saved) if saved.status == DcaStatus::Active => {}
+            _ => {
+                run.reason = Some("plan is no longer active".to_owned());
+                return run;
+            }
+        }
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, format!("Running plan #{}...", plan.id));
+
+        let receipt = Swap::send_simulated(tx, signer.wallet(), current_network);
+
+        sp.stop_with_newline();
+
+        match receipt {
+            Ok(receipt) => {
+                run.tx_hash = Some(receipt.transaction_hash);
+
+                if receipt.status == Some(U64::zero()) {
+                    run.status = DcaRunStatus::Failed;
+                    run.reason = Some("swap reverted".to_owned());
+                } else {
+                    run.status = DcaRunStatus::Filled;
+                }
+            }
+            Err(err) => {
+                run.status = DcaRunStatus::Failed;
+                run.reason = Some(err.to_string());
+            }
+        }
+
+        run
+    }
+
+    fn format_plan(plan: &DcaPlan) -> String {
+        format!(
+            "#{} {} {} -> {} every {}",
+            plan.id,
+            format_units(plan.amount_per_run, plan.token_in.decimals).unwrap(),
+            plan.token_in.symbol,
+            plan.token_out.symbol,
+            Self::format_duration(plan.schedule.interval)
+        )
+    }
+
+    fn format_duration(seconds: u64) -> String {
+        match seconds {
+            seconds if seconds >= 86400 && seconds % 86400 == 0 => format!("{}d", seconds / 86400),
+            seconds if seconds >= 86400 => {
+                format!("{}d {}h", seconds / 86400, seconds % 86400 / 3600)
+            }
+            seconds if seconds >= 3600 && seconds % 3600 == 0 => format!("{}h", seconds / 3600),
+            seconds if seconds >= 3600 => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+            seconds => format!("{}m", seconds / 60),
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use std::{
     io,
     io::{prelude::*, stdout},
+    process,
 };
 
-use console::Term;
+use console::{style, Term};
 use crossterm::execute;
 use dialoguer::{theme::ColorfulTheme, Select};
 use num_derive::FromPrimitive;
@@ -11,23 +12,35 @@ use num_traits::FromPrimitive;
 
 use crate::db::DB;
 use crate::network::Network;
+use crate::settings::Settings;
 
+use crate::address_book::AddressBook;
 use crate::wallet::storage;
 use account::AccountScreen;
+use address_book::AddressBookScreen;
 use network::NetworkScreen;
 use query::QueryScreen;
+use router::RouterScreen;
 use settings::SettingsScreen;
 use storage::WalletStorage;
 use swap::SwapScreen;
 use token::TokenScreen;
 
 pub mod account;
+pub mod address_book;
+pub mod benchmark;
+pub mod dca;
 pub mod network;
+pub mod order;
 pub mod query;
+pub mod router;
 pub mod settings;
 
+mod adapter;
+mod allowance;
 mod swap;
 mod token;
+mod watch;
 
 pub struct Terminal {}
 
@@ -38,6 +51,8 @@ enum StartScreens {
     Account,
     Network,
     Token,
+    AddressBook,
+    Router,
     Settings,
 }
 
@@ -59,7 +74,11 @@ impl Terminal {
         let mut address = "None".to_owned();
 
         if let Some(wallet) = current_wallet {
-            address = wallet.address.to_string();
+            address = if wallet.watch_only {
+                format!("{:?} (watch-only)", wallet.address)
+            } else {
+                wallet.address.to_string()
+            };
         }
 
         std::mem::drop(db_instance);
@@ -72,13 +91,24 @@ impl Terminal {
             address, current_network.name, current_network.chain_id, current_network.rpc_url
         );
         println!();
-        println!(
-            "Yak Router Contract: {}",
-            current_network
-                .yak_router
-                .unwrap_or_else(|| "None".to_owned())
-        );
+        let yak_router = match current_network.yak_router.as_ref() {
+            Some(yak_router) => match yak_router.parse() {
+                Ok(yak_router) => AddressBook::format_address(yak_router, current_network.chain_id),
+                Err(_) => yak_router.to_owned(),
+            },
+            None => "None".to_owned(),
+        };
+
+        println!("Yak Router Contract: {}", yak_router);
         println!();
+
+        if Settings::is_offline() {
+            println!(
+                "{}",
+                style("Offline mode: only cached token and adapter lists are used").yellow()
+            );
+            println!();
+        }
     }
 
     pub fn render_on_launch() {
@@ -100,7 +130,9 @@ impl Terminal {
             "3. Account",
             "4. Network",
             "5. Tokens",
-            "6. Settings",
+            "6. Address book",
+            "7. Router",
+            "8. Settings",
         ];
         let selection = Select::with_theme(&ColorfulTheme::default())
             .items(&start_screen_topics)
@@ -125,6 +157,12 @@ impl Terminal {
                 Some(StartScreens::Token) => {
                     TokenScreen::render();
                 }
+                Some(StartScreens::AddressBook) => {
+                    AddressBookScreen::render();
+                }
+                Some(StartScreens::Router) => {
+                    RouterScreen::render();
+                }
                 Some(StartScreens::Settings) => {
                     SettingsScreen::render();
                 }
@@ -143,6 +181,12 @@ impl Terminal {
         execute!(stdout(), crossterm::cursor::MoveTo(0, 0)).unwrap();
     }
 
+    // @dev subcommands end with non-zero exit code on errors, so scripts can check the result
+    pub fn exit_with_error(message: &str) -> ! {
+        eprintln!("{}", message);
+        process::exit(1)
+    }
+
     fn action_required() {
         let mut stdin = io::stdin();
         let mut stdout = io::stdout();
@@ -1,8 +1,13 @@
+use std::sync::Arc;
+
+use crate::token::Token;
+use crate::wallet::AccountWallet;
 use crate::Terminal;
-use console::Term;
-use dialoguer::{theme::ColorfulTheme, Input, Select};
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
 
 use crate::network::Network;
 
@@ -15,6 +20,7 @@ enum NetworkTopics {
     Set,
     UpdateRPC,
     UpdateRouter,
+    UpdateWrappedNative,
     Back,
 }
 
@@ -26,6 +32,7 @@ impl NetworkScreen {
             "1. Set current network",
             "2. Update network RPC URL",
             "3. Update network YAK Router Address",
+            "4. Update network wrapped native token address",
             "<- Go back",
         ];
 
@@ -57,6 +64,10 @@ impl NetworkScreen {
                     Self::update_router();
                     Terminal::render();
                 }
+                Some(NetworkTopics::UpdateWrappedNative) => {
+                    Self::update_wrapped_native();
+                    Terminal::render();
+                }
                 Some(NetworkTopics::Back) => {
                     Terminal::render();
                 }
@@ -153,4 +164,67 @@ impl NetworkScreen {
             panic!("Network not selected");
         }
     }
+
+    fn update_wrapped_native() {
+        let items = Network::get_supported_networks();
+
+        if items.is_empty() {
+            println!("Empty list of supported networks");
+            return;
+        }
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&items)
+            .with_prompt("Select chain to update wrapped native token for")
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        if let Some(selected) = selection {
+            let selected_network = items[selected].to_owned();
+
+            let wrapped_native = Input::<String>::new()
+                .with_prompt("New wrapped native token address")
+                .validate_with(|input: &String| -> Result<(), &str> {
+                    AccountWallet::parse_address(input).map(|_| ())
+                })
+                .interact_text()
+                .unwrap();
+
+            let network = Network::get_network(selected_network.chain_id)
+                .unwrap_or(selected_network.to_owned());
+
+            let mut sp = Spinner::new(Spinners::Aesthetic, "Validating wrapped native...".into());
+
+            let is_valid = Token::is_valid_native_wrapped(
+                AccountWallet::parse_address(&wrapped_native).unwrap(),
+                Arc::new(network),
+            );
+
+            sp.stop_with_newline();
+
+            // @dev invalid address is only saved on explicit confirmation, e.g. when RPC is down
+            if !is_valid {
+                println!(
+                    "{}",
+                    style("Address doesn't pass deposit/withdraw check, router WAVAX() will be used instead").red()
+                );
+
+                let is_confirmed = Confirm::new()
+                    .with_prompt("Save it anyway?")
+                    .default(false)
+                    .interact()
+                    .unwrap();
+
+                if !is_confirmed {
+                    println!("Wrapped native not updated");
+                    return;
+                }
+            }
+
+            Network::update_wrapped_native(selected_network.chain_id, wrapped_native);
+        } else {
+            panic!("Network not selected");
+        }
+    }
 }
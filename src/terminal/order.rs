@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::db::{reload_db, DB};
+use crate::network::Network;
+use crate::order::{LimitOrder, LimitOrders, OrderStatus};
+use crate::query::Query;
+use crate::settings::Settings;
+use crate::swap::Swap;
+use crate::token::Token;
+use crate::wallet::storage::WalletStorage;
+use crate::wallet::AccountWallet;
+use crate::Terminal;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
+use ethers::{
+    types::{H160, U64},
+    utils::{format_units, parse_units},
+};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
+
+use super::query::QueryScreen;
+use super::swap::SwapScreen;
+use super::watch::WatchScreen;
+
+pub struct LimitOrderScreen {}
+
+#[derive(FromPrimitive)]
+enum LimitOrderTopics {
+    Create,
+    List,
+    Cancel,
+    Run,
+    Back,
+}
+
+impl LimitOrderScreen {
+    const DEFAULT_INTERVAL: u64 = 30;
+
+    pub fn render() {
+        let topics = [
+            "1. Create limit order",
+            "2. List orders",
+            "3. Cancel order",
+            "4. Run order watcher",
+            "<- Go back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(LimitOrderTopics::Create) => {
+                    Self::create();
+                    Self::render();
+                }
+                Some(LimitOrderTopics::List) => {
+                    Self::list();
+                    Self::render();
+                }
+                Some(LimitOrderTopics::Cancel) => {
+                    Self::cancel();
+                    Self::render();
+                }
+                Some(LimitOrderTopics::Run) => {
+                    let interval = Input::<u64>::new()
+                        .with_prompt("Poll interval in seconds")
+                        .default(Self::DEFAULT_INTERVAL)
+                        .interact_text()
+                        .unwrap();
+
+                    if let Err(err) = Self::run(interval) {
+                        println!("{}", err);
+                    }
+                    Self::render();
+                }
+                Some(LimitOrderTopics::Back) => {
+                    Terminal::render();
+                }
+                None => panic!("Error while selecting limit order screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    // @dev `limit-orders [list | cancel <id> | run [--interval <seconds>]]`, exits with code 1 on errors
+    pub fn command(args: &[String]) {
+        let usage = "Usage: limit-orders [list | cancel <id> | run [--interval <seconds>]]";
+
+        let result = match args {
+            [] => {
+                Self::list();
+                Ok(())
+            }
+            [command] if command == "list" => {
+                Self::list();
+                Ok(())
+            }
+            [command, id] if command == "cancel" => match id.parse::<u32>() {
+                Ok(id) => Self::cancel_order(id),
+                Err(_) => Err(usage.to_owned()),
+            },
+            [command] if command == "run" => Self::run(Self::DEFAULT_INTERVAL),
+            [command, flag, interval] if command == "run" && flag == "--interval" => {
+                match interval.parse::<u64>() {
+                    Ok(interval) => Self::run(interval),
+                    Err(_) => Err(usage.to_owned()),
+                }
+            }
+            _ => Err(usage.to_owned()),
+        };
+
+        if let Err(err) = result {
+            Terminal::exit_with_error(&err);
+        }
+    }
+
+    fn create() {
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No wallet set");
+                return;
+            }
+        };
+
+        if current_wallet.watch_only {
+            println!(
+                "{}",
+                style("Current account is watch-only, watcher can't sign fills for it").red()
+            );
+            return;
+        }
+
+        let prompt_query = QueryScreen::prompt_query();
+
+        let token_in = prompt_query.token_in.address.parse::<H160>().unwrap();
+        let token_out = prompt_query.token_out.address.parse::<H160>().unwrap();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Getting current price...".into());
+
+        let offer = Query::find_best_path_with_gas(
+            prompt_query.amount_in,
+            token_in,
+            token_out,
+            prompt_query.max_steps,
+        );
+
+        sp.stop_with_newline();
+
+        let current_amount_out = match offer {
+            Ok(offer) if !offer.adapters.is_empty() => {
+                let amount_out = *offer.amounts.last().unwrap();
+
+                println!(
+                    "Current best output: {} {}",
+                    format_units(amount_out, prompt_query.token_out.decimals).unwrap(),
+                    prompt_query.token_out.symbol
+                );
+
+                Some(amount_out)
+            }
+            Ok(_) => {
+                println!("Yak path not found at current price");
+                None
+            }
+            Err(err) => {
+                println!("Error while getting best path: {}", err);
+                None
+            }
+        };
+
+        let token_out_decimals = prompt_query.token_out.decimals;
+
+        let mut min_out_input = Input::<String>::new();
+
+        min_out_input.with_prompt(format!(
+            "Minimum {} to receive",
+            prompt_query.token_out.symbol
+        ));
+
+        if let Some(amount_out) = current_amount_out {
+            min_out_input
+                .default(format_units(amount_out, prompt_query.token_out.decimals).unwrap());
+        }
+
+        let min_out_input = min_out_input
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match parse_units(input, token_out_decimals) {
+                    Ok(amount) if !amount.is_zero() => Ok(()),
+                    _ => Err("Wrong amount"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+
+        let min_amount_out = parse_units(min_out_input, prompt_query.token_out.decimals).unwrap();
+
+        let expiry_hours = Input::<u64>::new()
+            .with_prompt("Expires in hours")
+            .default(24)
+            .interact_text()
+            .unwrap();
+
+        let confirm = Confirm::new()
+            .with_prompt(format!(
+                "Swap {} {} to at least {} {} when price is met, valid {} hours?",
+                format_units(prompt_query.amount_in, prompt_query.token_in.decimals).unwrap(),
+                prompt_query.token_in.symbol,
+                format_units(min_amount_out, prompt_query.token_out.decimals).unwrap(),
+                prompt_query.token_out.symbol,
+                expiry_hours
+            ))
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        // @dev fill is simulated first, so missing allowance only keeps order open
+        if !Token::is_native(token_in) {
+            let yak_router_address = current_network
+                .yak_router
+                .as_ref()
+                .unwrap()
+                .parse::<H160>()
+                .unwrap();
+
+            let allowance = Token::get_allowance(
+                current_wallet.address,
+                yak_router_address,
+                token_in,
+                current_network.clone(),
+            );
+
+            if allowance < prompt_query.amount_in {
+                let approve = Confirm::new()
+                    .with_prompt("Router allowance is not enough to fill the order, approve now?")
+                    .default(true)
+                    .interact()
+                    .unwrap();
+
+                if approve {
+                    let password: String = Password::new()
+                        .with_prompt("Current Wallet password")
+                        .interact()
+                        .unwrap();
+
+                    if let Ok(wallet) =
+                        AccountWallet::decrypt_wallet(current_wallet.name.to_owned(), password)
+                    {
+                        SwapScreen::approve_router(
+                            &prompt_query,
+                            allowance,
+                            yak_router_address,
+                            wallet.wallet(),
+                            current_network.clone(),
+                        );
+                    }
+                } else {
+                    println!("Order fill will fail until router is approved");
+                }
+            }
+        }
+
+        let order = LimitOrders::create(
+            current_network.chain_id,
+            current_wallet.address,
+            prompt_query.token_in,
+            prompt_query.token_out,
+            prompt_query.amount_in,
+            min_amount_out,
+            LimitOrders::now() + expiry_hours * 3600,
+        );
+
+        println!(
+            "{} #{}, start order watcher to fill it",
+            style("Limit order created").green(),
+            order.id
+        );
+    }
+
+    fn list() {
+        let current_network = Network::get_current_network();
+
+        let orders = LimitOrders::get_orders(current_network.chain_id);
+
+        if orders.is_empty() {
+            println!("No limit orders for {}", current_network.name);
+            return;
+        }
+
+        let now = LimitOrders::now();
+
+        println!();
+        for order in &orders {
+            Self::print_order(order, now);
+
+            if let Some(tx_hash) = order.tx_hash {
+                println!(
+                    "      tx url: {explorer}/tx/{:?}",
+                    tx_hash,
+                    explorer = current_network.explorer_url
+                );
+            }
+
+            if let (OrderStatus::Open, Some(last_error)) = (&order.status, &order.last_error) {
+                println!("      last error: {}", style(last_error).red());
+            }
+        }
+        println!();
+    }
+
+    fn cancel() {
+        let current_network = Network::get_current_network();
+
+        let open_orders: Vec<LimitOrder> = LimitOrders::get_orders(current_network.chain_id)
+            .into_iter()
+            .filter(|order| order.status == OrderStatus::Open)
+            .collect();
+
+        if open_orders.is_empty() {
+            println!("No open limit orders");
+            return;
+        }
+
+        let order_items: Vec<String> = open_orders.iter().map(Self::format_order).collect();
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Order to cancel")
+            .items(&order_items)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        match selection {
+            Some(index) => {
+                if let Err(err) = Self::cancel_order(open_orders[index].id) {
+                    println!("{}", err);
+                }
+            }
+            None => println!("Ok, next time"),
+        }
+    }
+
+    fn cancel_order(id: u32) -> Result<(), String> {
+        let current_network = Network::get_current_network();
+
+        let order = LimitOrders::cancel(current_network.chain_id, id)?;
+
+        println!("{} #{}", style("Cancelled order").green(), order.id);
+
+        Ok(())
+    }
+
+    // @dev orders are re-read every poll and before every fill, so cancels from another session are picked up
+    fn run(interval: u64) -> Result<(), String> {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let open_orders: Vec<LimitOrder> = LimitOrders::get_orders(current_network.chain_id)
+            .into_iter()
+            .filter(|order| order.status == OrderStatus::Open)
+            .collect();
+
+        if open_orders.is_empty() {
+            println!("No open limit orders");
+            return Ok(());
+        }
+
+        let accounts: Vec<H160> = open_orders.iter().map(|order| order.account).collect();
+
+        let signers = Self::unlock_accounts(&accounts);
+
+        if signers.is_empty() {
+            return Err("No order account unlocked, watcher not started".to_owned());
+        }
+
+        println!(
+            "Watching {} open order(s) every {} seconds (press Enter to stop)",
+            open_orders.len(),
+            interval
+        );
+
+        let is_stopped = WatchScreen::listen_for_stop();
+
+        while !is_stopped.load(Ordering::Relaxed) {
+            let now = LimitOrders::now();
+
+            reload_db(&mut DB.lock().unwrap());
+
+            let open_orders = LimitOrders::get_orders(current_network.chain_id)
+                .into_iter()
+                .filter(|order| order.status == OrderStatus::Open)
+                .filter(|order| signers.contains_key(&order.account));
+
+            for mut order in open_orders {
+                if is_stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                Self::check_order(&mut order, now, &signers, current_network.clone());
+
+                if !LimitOrders::save_open_order(current_network.chain_id, &order) {
+                    println!(
+                        "{} #{}, watcher changes are not saved",
+                        style("Order is no longer open").yellow(),
+                        order.id
+                    );
+
+                    if let Some(tx_hash) = order.tx_hash {
+                        println!(
+                            "Fill was sent, tx url: {explorer}/tx/{:?}",
+                            tx_hash,
+                            explorer = current_network.explorer_url
+                        );
+                    }
+                }
+            }
+
+            let mut waited = 0;
+
+            while waited < interval && !is_stopped.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_secs(1));
+                waited += 1;
+            }
+        }
+
+        println!("Order watcher stopped");
+
+        Ok(())
+    }
+
+    // @dev each account is unlocked once, watch-only and wrong password are skipped
+    pub fn unlock_accounts(accounts: &[H160]) -> HashMap<H160, AccountWallet> {
+        let mut signers: HashMap<H160, AccountWallet> = HashMap::new();
+
+        for account in accounts {
+            if signers.contains_key(account) {
+                continue;
+            }
+
+            if let Some(account_wallet) = Self::unlock_account(*account) {
+                signers.insert(*account, account_wallet);
+            }
+        }
+
+        signers
+    }
+
+    pub fn unlock_account(account: H160) -> Option<AccountWallet> {
+        let wallet = match WalletStorage::get_wallets()
+            .into_iter()
+            .find(|wallet| wallet.address == account && !wallet.watch_only)
+        {
+            Some(wallet) => wallet,
+            None => {
+                println!(
+                    "{} {:?}, it is skipped",
+                    style("No signing account for").red(),
+                    account
+                );
+                return None;
+            }
+        };
+
+        let password: String = Password::new()
+            .with_prompt(format!("Password for {}", wallet.name))
+            .interact()
+            .unwrap();
+
+        AccountWallet::decrypt_wallet(wallet.name, password).ok()
+    }
+
+    fn check_order(
+        order: &mut LimitOrder,
+        now: u64,
+        signers: &HashMap<H160, AccountWallet>,
+        current_network: Arc<Network>,
+    ) {
+        if order.is_expired(now) {
+            order.status = OrderStatus::Expired;
+            println!("{} #{}", style("Order expired").yellow(), order.id);
+            return;
+        }
+
+        let offer = Query::find_best_path_with_gas(
+            order.amount_in,
+            order.token_in.address.parse::<H160>().unwrap(),
+            order.token_out.address.parse::<H160>().unwrap(),
+            Settings::get_max_steps(),
+        );
+
+        order.last_checked = Some(now);
+
+        let offer = match offer {
+            Ok(offer) if offer.adapters.is_empty() => {
+                order.last_amount_out = None;
+                order.last_error = Some("Yak path not found".to_owned());
+                Self::print_order(order, now);
+                return;
+            }
+            Ok(offer) => offer,
+            Err(err) => {
+                order.last_error = Some(err.to_string());
+                Self::print_order(order, now);
+                return;
+            }
+        };
+
+        let amount_out = *offer.amounts.last().unwrap();
+
+        order.last_amount_out = Some(amount_out);
+        order.last_error = None;
+
+        Self::print_order(order, now);
+
+        if amount_out < order.min_amount_out {
+            return;
+        }
+
+        let signer = match signers.get(&order.account) {
+            Some(signer) => signer,
+            None => return,
+        };
+
+        // @dev order may be cancelled in another session since this poll started
+        match LimitOrders::get_order(current_network.chain_id, order.id) {
+            Some(saved) if saved.status == OrderStatus::Open => {}
+            _ => return,
+        }
+
+        let tx = order.build_fill_tx(offer, current_network.clone());
+
+        let mut sp = Spinner::new(
+            Spinners::Aesthetic,
+            format!("Price met, filling order #{}...", order.id),
+        );
+
+        let fill = Swap::send_simulated(tx, signer.wallet(), current_network.clone());
+
+        sp.stop_with_newline();
+
+        match fill {
+            Ok(receipt) => {
+                order.tx_hash = Some(receipt.transaction_hash);
+
+                if receipt.status == Some(U64::zero()) {
+                    order.status = OrderStatus::Failed;
+                    println!("{} #{}", style("Fill reverted, order").red(), order.id);
+                } else {
+                    order.status = OrderStatus::Filled;
+                    println!("{} #{}", style("Hooray, filled order").green(), order.id);
+                }
+
+                println!(
+                    "tx url: {explorer}/tx/{:?}",
+                    receipt.transaction_hash,
+                    explorer = current_network.explorer_url
+                );
+            }
+            // @dev nothing sent, order stays open for next poll
+            Err(err) => {
+                order.last_error = Some(err.to_string());
+                println!("{} #{}: {}", style("Fill skipped").red(), order.id, err);
+            }
+        }
+    }
+
+    fn print_order(order: &LimitOrder, now: u64) {
+        let status = match order.status {
+            OrderStatus::Open if order.is_expired(now) => style("expired".to_owned()).yellow(),
+            OrderStatus::Open => style(format!(
+                "open, {}",
+                Self::format_remaining(order.expiry.saturating_sub(now))
+            )),
+            OrderStatus::Filled => style(order.status.to_string()).green(),
+            OrderStatus::Failed => style(order.status.to_string()).red(),
+            _ => style(order.status.to_string()).dim(),
+        };
+
+        let last_quote = match order.last_amount_out {
+            Some(last_amount_out) => format!(
+                ", last quote {} ({:.2}% of limit)",
+                format_units(last_amount_out, order.token_out.decimals).unwrap(),
+                last_amount_out.as_u128() as f64 / order.min_amount_out.as_u128() as f64 * 100.0
+            ),
+            None => String::new(),
+        };
+
+        println!("{}{} [{}]", Self::format_order(order), last_quote, status);
+    }
+
+    fn format_order(order: &LimitOrder) -> String {
+        format!(
+            "#{} {} {} -> min {} {}",
+            order.id,
+            format_units(order.amount_in, order.token_in.decimals).unwrap(),
+            order.token_in.symbol,
+            format_units(order.min_amount_out, order.token_out.decimals).unwrap(),
+            order.token_out.symbol
+        )
+    }
+
+    fn format_remaining(seconds: u64) -> String {
+        match seconds {
+            seconds if seconds >= 86400 => format!(
+                "expires in {}d {}h",
+                seconds / 86400,
+                seconds % 86400 / 3600
+            ),
+            seconds if seconds >= 3600 => {
+                format!("expires in {}h {}m", seconds / 3600, seconds % 3600 / 60)
+            }
+            seconds => format!("expires in {}m", seconds / 60),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::abis::FormattedOfferWithGas;
+use crate::address_book::AddressBook;
 use crate::db::DB;
 use crate::network::Network;
-use crate::query::adapters::Adapter;
 use crate::query::{ExternalQuote, ExternalQuoteError, Query};
 use crate::settings::Settings;
 use crate::swap::{FromToNative, Swap};
@@ -18,11 +18,14 @@ use ethers::{
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use spinners::{Spinner, Spinners};
+use std::collections::HashSet;
 use std::ops::Mul;
 use std::sync::Arc;
 
 use crate::wallet::storage::WalletStorage;
 
+use super::watch::{QuoteAlert, WatchOptions, WatchScreen};
+
 pub struct QueryScreen {}
 
 pub struct QueryPrompt {
@@ -35,7 +38,9 @@ pub struct QueryPrompt {
 #[derive(FromPrimitive)]
 enum QueryTopics {
     BestPath,
+    Watch,
     SingleAdapter,
+    CompareAdapters,
     List,
     Back,
 }
@@ -44,8 +49,10 @@ impl QueryScreen {
     pub fn render() {
         let topics = [
             "1. Query best path",
-            "2. Query single adapter",
-            "3. List adapters",
+            "2. Watch best path",
+            "3. Query single adapter",
+            "4. Compare adapters",
+            "5. List adapters",
             "<- Go back",
         ];
 
@@ -72,7 +79,9 @@ impl QueryScreen {
                     sp.stop_with_message("Finished getting best path ✅".to_owned());
 
                     // @dev start external price fetching
-                    let is_external_allowed = Settings::is_external_allowed();
+                    // @dev external quote always goes to network, so it's skipped in offline mode
+                    let is_external_allowed =
+                        Settings::is_external_allowed() && !Settings::is_offline();
 
                     let mut external_quote_result = ExternalQuote {
                         to_token_amount: String::default(),
@@ -161,6 +170,15 @@ impl QueryScreen {
 
                     Terminal::render();
                 }
+                Some(QueryTopics::Watch) => {
+                    let prompt_query = Self::prompt_query();
+
+                    let options = WatchScreen::prompt_options(&prompt_query.token_out);
+
+                    WatchScreen::watch(&prompt_query, &options);
+
+                    Self::render();
+                }
                 Some(QueryTopics::SingleAdapter) => {
                     let adapters = Query::get_adapters();
 
@@ -207,6 +225,10 @@ impl QueryScreen {
 
                     Self::render();
                 }
+                Some(QueryTopics::CompareAdapters) => {
+                    Self::compare_adapters();
+                    Self::render();
+                }
                 Some(QueryTopics::List) => {
                     let mut sp = Spinner::new(Spinners::Aesthetic, "Getting adapters...".into());
                     let adapters = Query::get_adapters();
@@ -238,8 +260,11 @@ impl QueryScreen {
 
         let tokens = crate::token::Token::get_tokens();
 
+        let duplicate_symbols = Token::get_duplicate_symbols(&tokens);
+        let token_items = Self::format_token_items(&tokens, &duplicate_symbols);
+
         let token_in_selection = FuzzySelect::with_theme(&ColorfulTheme::default())
-            .items(&tokens)
+            .items(&token_items)
             .with_prompt("Token in")
             .default(0)
             .interact_on_opt(&Term::stderr())
@@ -247,6 +272,8 @@ impl QueryScreen {
 
         let token_in = &tokens[token_in_selection.unwrap()];
 
+        Self::print_token_warnings(token_in, &duplicate_symbols);
+
         // @dev getting balance here is optional, so we dont want to panic if no account set or call failed
         if let Some(current_wallet) = current_wallet {
             let token_in_balance = Self::get_token_in_balance(token_in.to_owned(), current_wallet);
@@ -266,7 +293,7 @@ impl QueryScreen {
             .unwrap();
 
         let token_out_selection = FuzzySelect::with_theme(&ColorfulTheme::default())
-            .items(&tokens)
+            .items(&token_items)
             .with_prompt("Token out")
             .default(0)
             .interact_on_opt(&Term::stderr())
@@ -277,6 +304,8 @@ impl QueryScreen {
 
         let token_out = &tokens[token_out_selection.unwrap()];
 
+        Self::print_token_warnings(token_out, &duplicate_symbols);
+
         let amount_in = parse_units(amount_input, token_in.decimals).unwrap();
 
         QueryPrompt {
@@ -287,7 +316,223 @@ impl QueryScreen {
         }
     }
 
-    fn get_token_in_balance(token_in: Token, current_wallet: WalletStorage) -> Result<U256, ()> {
+    // @dev `quote <amount> <token in> <token out> [--watch] [--interval <seconds>] [--alert-above <amount>] [--alert-below <amount>]`,
+    // @dev exits with code 1 on errors
+    pub fn command(args: &[String]) {
+        let usage = "Usage: quote <amount> <token in> <token out> [--watch] [--interval <seconds>] [--alert-above <amount>] [--alert-below <amount>]";
+
+        let (amount_input, token_in_input, token_out_input) = match args {
+            [amount, token_in, token_out, ..] => (amount, token_in, token_out),
+            _ => Terminal::exit_with_error(usage),
+        };
+
+        let tokens = Token::get_tokens();
+
+        let (token_in, token_out) = match (
+            Token::find(&tokens, token_in_input),
+            Token::find(&tokens, token_out_input),
+        ) {
+            (Ok(token_in), Ok(token_out)) => (token_in, token_out),
+            (Err(err), _) => Terminal::exit_with_error(&format!("{}: {}", token_in_input, err)),
+            (_, Err(err)) => Terminal::exit_with_error(&format!("{}: {}", token_out_input, err)),
+        };
+
+        let amount_in = match parse_units(amount_input, token_in.decimals) {
+            Ok(amount_in) => amount_in,
+            Err(_) => Terminal::exit_with_error(&format!("Wrong amount {}", amount_input)),
+        };
+
+        let mut is_watch = false;
+        let mut options = WatchOptions {
+            interval: 0,
+            alert: None,
+        };
+
+        let mut args = args[3..].iter();
+
+        while let Some(arg) = args.next() {
+            let value = match arg.as_str() {
+                "--watch" => {
+                    is_watch = true;
+                    continue;
+                }
+                "--interval" | "--alert-above" | "--alert-below" => args.next(),
+                _ => None,
+            };
+
+            let value = match value {
+                Some(value) => value,
+                None => Terminal::exit_with_error(usage),
+            };
+
+            match arg.as_str() {
+                "--interval" => match value.parse::<u64>() {
+                    Ok(interval) => options.interval = interval,
+                    Err(_) => Terminal::exit_with_error(&format!("Wrong interval {}", value)),
+                },
+                _ => match parse_units(value, token_out.decimals) {
+                    Ok(threshold) if arg == "--alert-above" => {
+                        options.alert = Some(QuoteAlert::Above(threshold))
+                    }
+                    Ok(threshold) => options.alert = Some(QuoteAlert::Below(threshold)),
+                    Err(_) => Terminal::exit_with_error(&format!("Wrong threshold {}", value)),
+                },
+            }
+        }
+
+        let prompt_query = QueryPrompt {
+            amount_in,
+            token_in,
+            token_out,
+            max_steps: Settings::get_max_steps(),
+        };
+
+        if is_watch {
+            WatchScreen::watch(&prompt_query, &options);
+            return;
+        }
+
+        let find_path_result = Query::find_best_path_with_gas(
+            prompt_query.amount_in,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+            prompt_query.max_steps,
+        );
+
+        match find_path_result {
+            Ok(formatted_offer) if formatted_offer.adapters.is_empty() => {
+                Terminal::exit_with_error("Yak path not found 😔")
+            }
+            Ok(formatted_offer) => Self::format_offer_result(
+                formatted_offer,
+                prompt_query.token_out,
+                &Query::get_gas_price(),
+            ),
+            Err(err) => Terminal::exit_with_error(&err.to_string()),
+        }
+    }
+
+    fn compare_adapters() {
+        let prompt_query = Self::prompt_query();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Querying adapters...".into());
+
+        let adapter_quotes = Query::compare_adapters(
+            Query::get_adapters(),
+            prompt_query.amount_in,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        sp.stop_with_message("Finished ✅".to_owned());
+
+        let adapter_quotes = match adapter_quotes {
+            Ok(adapter_quotes) if adapter_quotes.is_empty() => {
+                println!("No adapter has a quote for this pair 😔");
+                return;
+            }
+            Ok(adapter_quotes) => adapter_quotes,
+            Err(err) => {
+                println!("Error while querying adapters: {}", err);
+                return;
+            }
+        };
+
+        let format_amount = |amount: U256| {
+            format!(
+                "{:.4}",
+                format_units(amount, prompt_query.token_out.decimals)
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap()
+            )
+        };
+
+        println!();
+        println!(
+            "{:<36} {:>20} {:>10} {:>20}",
+            "Adapter",
+            format!("Output {}", prompt_query.token_out.symbol),
+            "Gas",
+            "Net after gas"
+        );
+
+        for adapter_quote in adapter_quotes {
+            let net_amount_out = match adapter_quote.net_amount_out {
+                Some(net_amount_out) => format_amount(net_amount_out),
+                None => "n/a".to_owned(),
+            };
+
+            let line = format!(
+                "{:<36} {:>20} {:>10} {:>20}",
+                adapter_quote.adapter.name,
+                format_amount(adapter_quote.amount_out),
+                adapter_quote.gas_estimate,
+                net_amount_out
+            );
+
+            // @dev router queryNoSplit picks by output only, gas is not counted
+            if adapter_quote.is_router_pick {
+                println!(
+                    "{} {}",
+                    style(line).green(),
+                    style("<- queryNoSplit").green()
+                );
+            } else {
+                println!("{}", line);
+            }
+        }
+        println!();
+    }
+
+    // @dev scam clones usually copy symbol of a popular token and are not on trusted lists
+    fn format_token_items(tokens: &[Token], duplicate_symbols: &HashSet<String>) -> Vec<String> {
+        tokens
+            .iter()
+            .map(|token| {
+                let mut item = token.to_string();
+
+                if !token.is_trusted {
+                    item.push_str(" [untrusted]");
+                }
+
+                if duplicate_symbols.contains(&token.symbol.to_lowercase()) {
+                    item.push_str(" [duplicate symbol]");
+                }
+
+                item
+            })
+            .collect()
+    }
+
+    fn print_token_warnings(token: &Token, duplicate_symbols: &HashSet<String>) {
+        if !token.is_trusted {
+            println!(
+                "{}",
+                style(format!(
+                    "Warning: {} is not on a trusted token list, double check address {}",
+                    token.symbol, token.address
+                ))
+                .red()
+            );
+        }
+
+        if duplicate_symbols.contains(&token.symbol.to_lowercase()) {
+            println!(
+                "{}",
+                style(format!(
+                    "Warning: several tokens use symbol {}, make sure {} is the one you want",
+                    token.symbol, token.address
+                ))
+                .red()
+            );
+        }
+    }
+
+    pub fn get_token_in_balance(
+        token_in: Token,
+        current_wallet: WalletStorage,
+    ) -> Result<U256, ()> {
         let current_network = Arc::new(Network::get_current_network());
 
         let from_to_native =
@@ -339,7 +584,11 @@ impl QueryScreen {
                 let token = tokens
                     .iter()
                     .find(|token| token.address.parse::<H160>().unwrap() == addr);
-                token.unwrap_or(&Token::unknown()).to_string()
+
+                match token {
+                    Some(token) => token.to_string(),
+                    None => AddressBook::format_address(addr, current_network.chain_id),
+                }
             })
             .collect::<Vec<String>>();
 
@@ -352,12 +601,11 @@ impl QueryScreen {
                 let adapter = network_adapters
                     .iter()
                     .find(|adapter| adapter.address == addr);
-                adapter
-                    .unwrap_or(&Adapter {
-                        name: "Unknown".to_owned(),
-                        address: H160::zero(),
-                    })
-                    .to_string()
+
+                match adapter {
+                    Some(adapter) => adapter.to_string(),
+                    None => AddressBook::format_address(addr, current_network.chain_id),
+                }
             })
             .collect::<Vec<String>>();
 
@@ -0,0 +1,577 @@
+use std::sync::Arc;
+
+use crate::address_book::AddressBook;
+use crate::admin::Admin;
+use crate::network::Network;
+use crate::router::{Router, RouterAdminCall, RouterInfo};
+use crate::safe::{Safe, SafeTransaction};
+use crate::settings::Settings;
+use crate::token::Token;
+use crate::wallet::storage::WalletStorage;
+use crate::Terminal;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
+use ethers::types::{transaction::eip2718::TypedTransaction, H160, U256};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
+
+use super::adapter::AdapterScreen;
+use super::benchmark::BenchmarkScreen;
+
+pub struct RouterScreen {}
+
+#[derive(FromPrimitive)]
+enum RouterTopics {
+    Info,
+    Export,
+    Diff,
+    Admin,
+    AdapterAdmin,
+    Benchmark,
+    BenchmarkHistory,
+    Back,
+}
+
+impl RouterScreen {
+    pub fn render() {
+        let is_owner = Self::is_current_owner();
+
+        let mut topics = vec![
+            "1. Router info".to_owned(),
+            "2. Export router info to JSON".to_owned(),
+            "3. Diff router info against snapshot".to_owned(),
+        ];
+
+        // @dev admin mode is only offered to router owner
+        if is_owner {
+            topics.push("4. Router admin".to_owned());
+        }
+
+        topics.push(format!("{}. Adapter admin", topics.len() + 1));
+        topics.push(format!("{}. Benchmark adapter gas", topics.len() + 1));
+        topics.push(format!("{}. Benchmark history", topics.len() + 1));
+        topics.push("<- Go back".to_owned());
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let selection = selection.map(|index| {
+            if !is_owner && index >= RouterTopics::Admin as usize {
+                index + 1
+            } else {
+                index
+            }
+        });
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(RouterTopics::Info) => {
+                    if let Some(info) = Self::load_info() {
+                        Self::print_info(&info);
+                    }
+                    Self::render();
+                }
+                Some(RouterTopics::Export) => {
+                    let path = Input::<String>::new()
+                        .with_prompt("Export router info to file")
+                        .default("router-info.json".to_owned())
+                        .interact_text()
+                        .unwrap();
+
+                    if let Some(info) = Self::load_info() {
+                        if let Err(err) = Self::export_info(&info, &path) {
+                            println!("{}", err);
+                        }
+                    }
+                    Self::render();
+                }
+                Some(RouterTopics::Diff) => {
+                    let path = Input::<String>::new()
+                        .with_prompt("Snapshot file")
+                        .default("router-info.json".to_owned())
+                        .interact_text()
+                        .unwrap();
+
+                    if let Some(info) = Self::load_info() {
+                        if let Err(err) = Self::print_diff(&info, &path, false) {
+                            println!("{}", err);
+                        }
+                    }
+                    Self::render();
+                }
+                Some(RouterTopics::Admin) => {
+                    Self::admin();
+                    Terminal::render();
+                }
+                Some(RouterTopics::AdapterAdmin) => {
+                    AdapterScreen::admin();
+                    Terminal::render();
+                }
+                Some(RouterTopics::Benchmark) => {
+                    BenchmarkScreen::render();
+                    Self::render();
+                }
+                Some(RouterTopics::BenchmarkHistory) => {
+                    BenchmarkScreen::command(&["--history".to_owned()]);
+                    Self::render();
+                }
+                Some(RouterTopics::Back) => {
+                    Terminal::render();
+                }
+                None => panic!("Error while selecting router screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    // @dev `router-info [--json] [--export <file>] [--diff <file>]`, with `--json` diff is printed as JSON.
+    // @dev exits with code 1 on errors
+    pub fn command(args: &[String]) {
+        let usage = "Usage: router-info [--json] [--export <file>] [--diff <file>]";
+
+        let mut is_json = false;
+        let mut export_path: Option<String> = None;
+        let mut diff_path: Option<String> = None;
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let path = match arg.as_str() {
+                "--json" => {
+                    is_json = true;
+                    continue;
+                }
+                "--export" => &mut export_path,
+                "--diff" => &mut diff_path,
+                _ => Terminal::exit_with_error(usage),
+            };
+
+            match args.next() {
+                Some(value) if !value.starts_with("--") => *path = Some(value.to_owned()),
+                _ => Terminal::exit_with_error(&format!("Missing file for {}\n{}", arg, usage)),
+            }
+        }
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let info = match Router::get_info(current_network) {
+            Ok(info) => info,
+            Err(err) => Terminal::exit_with_error(&format!("Error while reading router: {}", err)),
+        };
+
+        if let Some(path) = export_path {
+            if let Err(err) = Self::export_info(&info, &path) {
+                Terminal::exit_with_error(&err);
+            }
+        }
+
+        let result = match diff_path {
+            Some(path) => Self::print_diff(&info, &path, is_json),
+            None if is_json => {
+                println!("{}", serde_json::to_string_pretty(&info).unwrap());
+                Ok(())
+            }
+            None => {
+                Self::print_info(&info);
+                Ok(())
+            }
+        };
+
+        if let Err(err) = result {
+            Terminal::exit_with_error(&err);
+        }
+    }
+
+    fn is_current_owner() -> bool {
+        if Settings::is_offline() {
+            return false;
+        }
+
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => return false,
+        };
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        match Router::get_owner(current_network) {
+            Ok(owner) => owner == current_wallet.address,
+            Err(_) => false,
+        }
+    }
+
+    fn admin() {
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No current wallet set");
+                return;
+            }
+        };
+
+        let info = match Self::load_info() {
+            Some(info) => info,
+            None => return,
+        };
+
+        if info.owner != current_wallet.address {
+            println!("Current account is not router owner");
+            return;
+        }
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let call = match Self::prompt_admin_call(&info, current_network.clone()) {
+            Some(call) => call,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        println!();
+        println!("Call: {}", style(&call).yellow());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Reading new entries...".into());
+
+        let proposed = Router::preview_admin_call(&call, &info, current_network.clone());
+
+        sp.stop_with_newline();
+
+        let changes = proposed.diff(&info);
+
+        for change in &changes {
+            println!("{}", style(change).yellow());
+        }
+        println!();
+
+        let tx = Router::build_admin_tx(&call, info.address, current_network.clone());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Simulating...".into());
+
+        let simulation = Admin::simulate(tx.to_owned(), info.owner, current_network.clone());
+
+        sp.stop_with_newline();
+
+        match simulation {
+            Ok(gas) => println!(
+                "{} gas estimate: {}",
+                style("Simulation succeeded,").green(),
+                gas
+            ),
+            Err(err) => {
+                println!("{} {}", style("Simulation failed:").red(), err);
+                return;
+            }
+        }
+
+        Self::send_admin_tx(
+            tx,
+            &current_wallet,
+            current_network,
+            "Yak Router admin",
+            "safe-router-admin.json",
+        );
+    }
+
+    fn prompt_admin_call(
+        info: &RouterInfo,
+        current_network: Arc<Network>,
+    ) -> Option<RouterAdminCall> {
+        let actions = [
+            "setAdapters",
+            "setTrustedTokens",
+            "setMinFee",
+            "setFeeClaimer",
+            "recoverERC20",
+            "recoverAVAX (native token)",
+            "transferOwnership",
+        ];
+
+        let action = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Router admin call")
+            .items(&actions)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap()?;
+
+        match action {
+            0 => Some(RouterAdminCall::SetAdapters(Self::prompt_addresses(
+                "Adapters (comma separated, in router order)",
+                info.adapters
+                    .iter()
+                    .map(|adapter| adapter.address)
+                    .collect(),
+            ))),
+            1 => Some(RouterAdminCall::SetTrustedTokens(Self::prompt_addresses(
+                "Trusted tokens (comma separated)",
+                info.trusted_tokens
+                    .iter()
+                    .map(|token| token.address)
+                    .collect(),
+            ))),
+            2 => {
+                let fee = Input::<String>::new()
+                    .with_prompt("MIN_FEE")
+                    .default(info.min_fee.to_string())
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        U256::from_dec_str(input)
+                            .map(|_| ())
+                            .map_err(|_| "Wrong fee")
+                    })
+                    .interact_text()
+                    .unwrap();
+
+                Some(RouterAdminCall::SetMinFee(
+                    U256::from_dec_str(&fee).unwrap(),
+                ))
+            }
+            3 => Some(RouterAdminCall::SetFeeClaimer(Self::prompt_address(
+                "New FEE_CLAIMER",
+            ))),
+            4 => {
+                let token = Self::prompt_address("Token to recover");
+
+                let balance = Token::get_token_balance(info.address, token, current_network);
+
+                Some(RouterAdminCall::RecoverERC20(
+                    token,
+                    Self::prompt_amount(balance),
+                ))
+            }
+            5 => {
+                let balance = Token::get_native_balance(info.address, current_network);
+
+                Some(RouterAdminCall::RecoverAVAX(Self::prompt_amount(balance)))
+            }
+            6 => {
+                let new_owner = Self::prompt_address("New owner");
+
+                let confirm = Confirm::new()
+                    .with_prompt(format!(
+                        "Router will be owned by {}, current account loses admin access. Continue?",
+                        AddressBook::format_address(new_owner, info.chain_id)
+                    ))
+                    .default(false)
+                    .interact()
+                    .unwrap();
+
+                if confirm {
+                    Some(RouterAdminCall::TransferOwnership(new_owner))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn prompt_addresses(prompt: &str, current: Vec<H160>) -> Vec<H160> {
+        let current = current
+            .iter()
+            .map(|address| format!("{:?}", address))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let input = Input::<String>::new()
+            .with_prompt(prompt)
+            .default(current)
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match input
+                    .split(',')
+                    .all(|address| address.trim().parse::<H160>().is_ok())
+                {
+                    true => Ok(()),
+                    false => Err("Wrong address in list"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+
+        input
+            .split(',')
+            .map(|address| address.trim().parse::<H160>().unwrap())
+            .collect()
+    }
+
+    pub fn prompt_address(prompt: &str) -> H160 {
+        Input::<String>::new()
+            .with_prompt(prompt)
+            .validate_with(|input: &String| -> Result<(), &str> {
+                input
+                    .trim()
+                    .parse::<H160>()
+                    .map(|_| ())
+                    .map_err(|_| "Wrong address")
+            })
+            .interact_text()
+            .unwrap()
+            .trim()
+            .parse::<H160>()
+            .unwrap()
+    }
+
+    // @dev raw amount in token units, defaults to whole contract balance
+    pub fn prompt_amount(balance: U256) -> U256 {
+        let amount = Input::<String>::new()
+            .with_prompt("Amount (raw units)")
+            .default(balance.to_string())
+            .validate_with(|input: &String| -> Result<(), &str> {
+                U256::from_dec_str(input)
+                    .map(|_| ())
+                    .map_err(|_| "Wrong amount")
+            })
+            .interact_text()
+            .unwrap();
+
+        U256::from_dec_str(&amount).unwrap()
+    }
+
+    // @dev watch-only owner is usually a Safe, call goes to Transaction Builder batch then
+    pub fn send_admin_tx(
+        tx: TypedTransaction,
+        current_wallet: &WalletStorage,
+        current_network: Arc<Network>,
+        description: &str,
+        default_path: &str,
+    ) {
+        if current_wallet.watch_only {
+            let batch = Safe::build_batch(
+                current_wallet.address,
+                current_network.chain_id,
+                description,
+                &[SafeTransaction::from(tx)],
+            );
+
+            let path = Input::<String>::new()
+                .with_prompt("Watch-only account, export Safe batch to file")
+                .default(default_path.to_owned())
+                .interact_text()
+                .unwrap();
+
+            match Safe::export_batch(&batch, &path) {
+                Ok(_) => println!("{} {}", style("Safe batch exported to").green(), path),
+                Err(err) => println!("Error while exporting batch: {}", err),
+            }
+
+            return;
+        }
+
+        let confirm = Confirm::new()
+            .with_prompt("Send transaction?")
+            .default(false)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let password: String = Password::new()
+            .with_prompt("Current Wallet password")
+            .interact()
+            .unwrap();
+
+        let wallet =
+            crate::wallet::AccountWallet::decrypt_wallet(current_wallet.name.to_owned(), password);
+
+        if wallet.is_err() {
+            return;
+        }
+
+        let wallet = wallet.expect("Something wrong with wallet");
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Sending...".into());
+
+        let receipt = Admin::send(tx, wallet.wallet(), current_network.clone());
+
+        sp.stop_with_newline();
+
+        match receipt {
+            Some(receipt) => println!(
+                "tx url: {explorer}/tx/{:?}",
+                receipt.transaction_hash,
+                explorer = current_network.explorer_url
+            ),
+            None => println!("Error when getting tx hash on admin call"),
+        }
+    }
+
+    fn load_info() -> Option<RouterInfo> {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Reading router...".into());
+
+        let info = Router::get_info(current_network);
+
+        sp.stop_with_newline();
+
+        match info {
+            Ok(info) => Some(info),
+            Err(err) => {
+                println!("Error while reading router: {}", err);
+                None
+            }
+        }
+    }
+
+    fn print_info(info: &RouterInfo) {
+        let format_address = |address| AddressBook::format_address(address, info.chain_id);
+
+        println!();
+        println!("Router: {}", format_address(info.address));
+        println!("Owner: {}", format_address(info.owner));
+        println!("FEE_CLAIMER: {}", format_address(info.fee_claimer));
+        println!("MIN_FEE: {}", info.min_fee);
+        println!();
+        println!("Adapters ({}):", info.adapters_count);
+        for (index, adapter) in info.adapters.iter().enumerate() {
+            println!(
+                "{:>3}. {:<32} {:?} \t gas estimate: {}",
+                index, adapter.name, adapter.address, adapter.gas_estimate
+            );
+        }
+        println!();
+        println!("Trusted tokens ({}):", info.trusted_tokens.len());
+        for (index, token) in info.trusted_tokens.iter().enumerate() {
+            println!("{:>3}. {:<12} {:?}", index, token.symbol, token.address);
+        }
+        println!();
+    }
+
+    fn export_info(info: &RouterInfo, path: &str) -> Result<(), String> {
+        Router::export_info(info, path)
+            .map(|_| eprintln!("{} {}", style("Router info exported to").green(), path))
+            .map_err(|err| format!("Error while exporting router info: {}", err))
+    }
+
+    fn print_diff(info: &RouterInfo, path: &str, is_json: bool) -> Result<(), String> {
+        let previous = Router::import_info(path)
+            .map_err(|err| format!("Error while reading snapshot: {}", err))?;
+
+        let changes = info.diff(&previous);
+
+        if is_json {
+            println!("{}", serde_json::to_string_pretty(&changes).unwrap());
+            return Ok(());
+        }
+
+        println!();
+        if changes.is_empty() {
+            println!("{}", style("Router configuration matches snapshot").green());
+        } else {
+            println!("Changes since {}:", path);
+            for change in changes {
+                println!("{}", style(change).yellow());
+            }
+        }
+        println!();
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{settings::Settings, Terminal};
-use console::Term;
-use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+use crate::{
+    cache::Cache,
+    settings::{ApprovalPolicy, Settings},
+    Terminal,
+};
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
@@ -11,6 +15,11 @@ enum SettingsTopics {
     Hops,
     Slippage,
     External,
+    CacheTtl,
+    Offline,
+    RefreshCache,
+    ApprovalPolicy,
+    PermitDeadline,
     Back,
 }
 
@@ -20,6 +29,11 @@ impl SettingsScreen {
             "1. Path hops",
             "2. Slippage tolerance",
             "3. Compare with external quote",
+            "4. Token and adapter lists cache TTL",
+            "5. Offline mode",
+            "6. Refresh cached token and adapter lists",
+            "7. Approval policy",
+            "8. Permit deadline",
             "<- Go back",
         ];
 
@@ -49,6 +63,46 @@ impl SettingsScreen {
 
                     Terminal::render();
                 }
+                Some(SettingsTopics::CacheTtl) => {
+                    let cache_ttl = Self::select_cache_ttl();
+                    Settings::set_cache_ttl(cache_ttl);
+
+                    Terminal::render();
+                }
+                Some(SettingsTopics::Offline) => {
+                    let is_offline = Self::confirm_is_offline();
+                    Settings::set_is_offline(is_offline);
+
+                    Terminal::render();
+                }
+                Some(SettingsTopics::RefreshCache) => {
+                    let cleared = Cache::clear();
+                    println!(
+                        "{}",
+                        style(format!(
+                            "Cleared {} cached lists, they will be fetched on next use",
+                            cleared
+                        ))
+                        .green()
+                    );
+
+                    Terminal::render();
+                }
+                Some(SettingsTopics::ApprovalPolicy) => {
+                    if let Some(approval_policy) =
+                        Self::select_approval_policy(&Settings::get_approval_policy())
+                    {
+                        Settings::set_approval_policy(approval_policy);
+                    }
+
+                    Terminal::render();
+                }
+                Some(SettingsTopics::PermitDeadline) => {
+                    let permit_deadline = Self::select_permit_deadline();
+                    Settings::set_permit_deadline(permit_deadline);
+
+                    Terminal::render();
+                }
                 Some(SettingsTopics::Back) => {
                     Terminal::render();
                 }
@@ -58,6 +112,46 @@ impl SettingsScreen {
         }
     }
 
+    // @dev also used per swap, None when user cancelled
+    pub fn select_approval_policy(current_policy: &ApprovalPolicy) -> Option<ApprovalPolicy> {
+        let current_buffer = match current_policy {
+            ApprovalPolicy::ExactWithBuffer(buffer) => *buffer,
+            _ => 10,
+        };
+
+        let policy_items = [
+            ApprovalPolicy::Exact,
+            ApprovalPolicy::ExactWithBuffer(current_buffer),
+            ApprovalPolicy::Unlimited,
+        ];
+
+        let policy_select_index = match current_policy {
+            ApprovalPolicy::Exact => 0,
+            ApprovalPolicy::ExactWithBuffer(_) => 1,
+            ApprovalPolicy::Unlimited => 2,
+        };
+
+        let policy_selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&policy_items)
+            .with_prompt("How much router is allowed to spend")
+            .default(policy_select_index)
+            .interact_on_opt(&Term::stderr())
+            .unwrap()?;
+
+        match policy_items[policy_selection] {
+            ApprovalPolicy::ExactWithBuffer(_) => {
+                let buffer = Input::<u32>::new()
+                    .with_prompt("Buffer on top of swap amount, %")
+                    .default(current_buffer)
+                    .interact_text()
+                    .unwrap();
+
+                Some(ApprovalPolicy::ExactWithBuffer(buffer))
+            }
+            ref approval_policy => Some(approval_policy.to_owned()),
+        }
+    }
+
     fn select_max_steps() -> i32 {
         let current_max_steps = Settings::get_max_steps();
 
@@ -81,8 +175,8 @@ impl SettingsScreen {
     fn select_slippage() -> u32 {
         let current_slippage = Settings::get_slippage();
 
-        let slippage_items = vec![1, 5, 10];
-        let slippage_items_format = vec!["0.1%", "0.5%", "1%"];
+        let slippage_items = [1, 5, 10];
+        let slippage_items_format = ["0.1%", "0.5%", "1%"];
 
         let slippage_select_index = slippage_items
             .iter()
@@ -99,6 +193,60 @@ impl SettingsScreen {
         slippage_items[slippage_selection.unwrap()]
     }
 
+    fn select_permit_deadline() -> u32 {
+        let current_permit_deadline = Settings::get_permit_deadline();
+
+        let permit_deadline_items = [5, 10, 20, 60];
+        let permit_deadline_items_format = ["5 minutes", "10 minutes", "20 minutes", "1 hour"];
+
+        let permit_deadline_select_index = permit_deadline_items
+            .iter()
+            .position(|&i| i == current_permit_deadline)
+            .unwrap_or(2);
+
+        let permit_deadline_selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&permit_deadline_items_format)
+            .with_prompt("How long permit signature stays valid after latest block")
+            .default(permit_deadline_select_index)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        permit_deadline_items[permit_deadline_selection.unwrap()]
+    }
+
+    fn select_cache_ttl() -> u32 {
+        let current_cache_ttl = Settings::get_cache_ttl();
+
+        let cache_ttl_items = [0, 10, 60, 360, 1440];
+        let cache_ttl_items_format = ["No cache", "10 minutes", "1 hour", "6 hours", "1 day"];
+
+        let cache_ttl_select_index = cache_ttl_items
+            .iter()
+            .position(|&i| i == current_cache_ttl)
+            .unwrap_or(2);
+
+        let cache_ttl_selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&cache_ttl_items_format)
+            .with_prompt("How long to keep token and adapter lists before fetching again")
+            .default(cache_ttl_select_index)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        cache_ttl_items[cache_ttl_selection.unwrap()]
+    }
+
+    fn confirm_is_offline() -> bool {
+        let is_offline = Settings::is_offline();
+
+        let confirm_is_offline = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to use only cached token and adapter lists (offline mode)?")
+            .default(is_offline)
+            .interact()
+            .unwrap();
+
+        confirm_is_offline
+    }
+
     fn confirm_is_external_allowed() -> bool {
         let is_external_allowed = Settings::is_external_allowed();
 
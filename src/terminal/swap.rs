@@ -1,26 +1,37 @@
 use std::sync::Arc;
 
-use crate::abis::Trade;
+use crate::abis::{FormattedOfferWithGas, Trade};
+use crate::address_book::{AddressBook, AddressBookEntry};
 use crate::db::DB;
 use crate::network::Network;
+use crate::offline::{Offline, UnsignedTransaction};
+use crate::permit::{Permit, PermitKind, TokenPermit};
 use crate::query::{ExternalQuote, ExternalQuoteError, Query};
+use crate::safe::{Safe, SafeTransaction};
 use crate::settings::Settings;
 use crate::swap::{FromToNative, Swap};
 use crate::terminal::storage::WalletStorage;
 use crate::token::Token;
+use crate::wallet::AccountWallet;
 use crate::Terminal;
 use console::{style, Term};
-use dialoguer::{theme::ColorfulTheme, Input, Select};
+use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input, Select};
 use dialoguer::{Confirm, Password};
 use ethers::{
-    types::{H160, U256},
-    utils::parse_units,
+    prelude::k256::ecdsa::SigningKey,
+    signers::Wallet,
+    types::{H160, U256, U64},
+    utils::{format_units, parse_units},
 };
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use spinners::{Spinner, Spinners};
 
-use super::query::QueryScreen;
+use super::address_book::AddressBookScreen;
+use super::dca::DcaScreen;
+use super::order::LimitOrderScreen;
+use super::query::{QueryPrompt, QueryScreen};
+use super::settings::SettingsScreen;
 
 use crate::wallet::storage;
 
@@ -29,8 +40,14 @@ pub struct SwapScreen {}
 #[derive(FromPrimitive)]
 enum SwapTopics {
     Swap,
+    ExploreRoutes,
+    SplitOrder,
+    LimitOrders,
+    Dca,
     WrapNative,
     UnwrapNative,
+    SignOffline,
+    Broadcast,
     Back,
 }
 
@@ -38,8 +55,14 @@ impl SwapScreen {
     pub fn render() {
         let topics = [
             "1. Swap tokens",
-            "2. Wrap native token",
-            "3. Unwrap native token",
+            "2. Explore routes and swap",
+            "3. Split order across routes",
+            "4. Limit orders",
+            "5. DCA plans",
+            "6. Wrap native token",
+            "7. Unwrap native token",
+            "8. Sign exported transaction",
+            "9. Broadcast signed transaction",
             "<- Go back",
         ];
 
@@ -55,6 +78,20 @@ impl SwapScreen {
                     Self::swap();
                     Terminal::render();
                 }
+                Some(SwapTopics::ExploreRoutes) => {
+                    Self::explore_routes();
+                    Terminal::render();
+                }
+                Some(SwapTopics::SplitOrder) => {
+                    Self::split_order();
+                    Terminal::render();
+                }
+                Some(SwapTopics::LimitOrders) => {
+                    LimitOrderScreen::render();
+                }
+                Some(SwapTopics::Dca) => {
+                    DcaScreen::render();
+                }
                 Some(SwapTopics::WrapNative) => {
                     Self::wrap_native();
                     Terminal::render();
@@ -63,6 +100,14 @@ impl SwapScreen {
                     Self::unwrap_native();
                     Terminal::render();
                 }
+                Some(SwapTopics::SignOffline) => {
+                    Self::sign_offline();
+                    Terminal::render();
+                }
+                Some(SwapTopics::Broadcast) => {
+                    Self::broadcast();
+                    Terminal::render();
+                }
                 Some(SwapTopics::Back) => {
                     Terminal::render();
                 }
@@ -89,7 +134,8 @@ impl SwapScreen {
         let formatted_offer = find_path_result.ok().expect("Error when getting best path");
 
         // @dev start external price fetching
-        let is_external_allowed = Settings::is_external_allowed();
+        // @dev external quote always goes to network, so it's skipped in offline mode
+        let is_external_allowed = Settings::is_external_allowed() && !Settings::is_offline();
 
         let mut external_quote_result = ExternalQuote {
             to_token_amount: String::default(),
@@ -160,6 +206,441 @@ impl SwapScreen {
             );
         }
 
+        Self::swap_offer(formatted_offer, prompt_query);
+    }
+
+    // @dev top routes through router trusted tokens, picked one is sent as swapNoSplit trade
+    fn explore_routes() {
+        let prompt_query = QueryScreen::prompt_query();
+
+        let top = Input::<usize>::new()
+            .with_prompt("Number of routes to show")
+            .default(5)
+            .interact_text()
+            .unwrap();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Exploring routes...".into());
+
+        let route_offers = Query::explore_routes(
+            prompt_query.amount_in,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+            prompt_query.max_steps,
+            top,
+        );
+
+        sp.stop_with_newline();
+
+        let exploration = match route_offers {
+            Ok(exploration) => exploration,
+            Err(err) => {
+                println!("Error while exploring routes: {}", err);
+                return;
+            }
+        };
+
+        Self::print_failed_queries(exploration.failed_queries);
+
+        if exploration.routes.is_empty() {
+            println!("Path not found 😔");
+            return;
+        }
+
+        let mut route_offers = exploration.routes;
+
+        let tokens = Token::get_tokens();
+        let adapters = Query::get_adapters();
+
+        let format_amount = |amount: U256| {
+            format!(
+                "{:.4}",
+                format_units(amount, prompt_query.token_out.decimals)
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap()
+            )
+        };
+
+        let route_items: Vec<String> = route_offers
+            .iter()
+            .map(|route_offer| {
+                let hops: Vec<String> = route_offer
+                    .offer
+                    .path
+                    .iter()
+                    .skip(1)
+                    .zip(&route_offer.offer.adapters)
+                    .map(|(token, adapter)| {
+                        let symbol = tokens
+                            .iter()
+                            .find(|known| known.address.parse::<H160>().ok() == Some(*token))
+                            .map(|known| known.symbol.to_owned())
+                            .unwrap_or_else(|| format!("{:?}", token));
+                        let adapter_name = adapters
+                            .iter()
+                            .find(|known| known.address == *adapter)
+                            .map(|known| known.name.to_owned())
+                            .unwrap_or_else(|| format!("{:?}", adapter));
+
+                        format!("{} ({})", symbol, adapter_name)
+                    })
+                    .collect();
+
+                let net_amount_out = match route_offer.net_amount_out {
+                    Some(net_amount_out) => format_amount(net_amount_out),
+                    None => "n/a".to_owned(),
+                };
+
+                format!(
+                    "{} {} (net {}), gas {}: {} => {}",
+                    format_amount(*route_offer.offer.amounts.last().unwrap()),
+                    prompt_query.token_out.symbol,
+                    net_amount_out,
+                    route_offer.offer.gas_estimate,
+                    prompt_query.token_in.symbol,
+                    hops.join(" => ")
+                )
+            })
+            .collect();
+
+        let route_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Routes ranked by output after gas, pick one to swap")
+            .items(&route_items)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let formatted_offer = match route_selection {
+            Some(index) => route_offers.swap_remove(index).offer,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        QueryScreen::format_offer_result(
+            formatted_offer.to_owned(),
+            prompt_query.token_out.to_owned(),
+            &Query::get_gas_price(),
+        );
+
+        Self::swap_offer(formatted_offer, prompt_query);
+    }
+
+    fn print_failed_queries(failed_queries: usize) {
+        if failed_queries > 0 {
+            println!(
+                "{}",
+                style(format!(
+                    "{} adapter queries failed, routes through them are missing",
+                    failed_queries
+                ))
+                .yellow()
+            );
+        }
+    }
+
+    // @dev amount is split over explored routes, legs are sent as separate swapNoSplit transactions
+    fn split_order() {
+        let prompt_query = QueryScreen::prompt_query();
+
+        let parts = Input::<u32>::new()
+            .with_prompt("Split amount into parts")
+            .default(10)
+            .interact_text()
+            .unwrap();
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Exploring routes...".into());
+
+        let route_offers = Query::explore_routes(
+            prompt_query.amount_in,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+            prompt_query.max_steps,
+            5,
+        );
+
+        sp.stop_with_newline();
+
+        let exploration = match route_offers {
+            Ok(exploration) => exploration,
+            Err(err) => {
+                println!("Error while exploring routes: {}", err);
+                return;
+            }
+        };
+
+        Self::print_failed_queries(exploration.failed_queries);
+
+        if exploration.routes.is_empty() {
+            println!("Path not found 😔");
+            return;
+        }
+
+        let route_offers = exploration.routes;
+
+        let single_offer = route_offers[0].offer.to_owned();
+        let single_amount_out = *single_offer.amounts.last().unwrap();
+        let single_net_amount_out = route_offers[0].net_amount_out;
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Splitting order...".into());
+
+        let split_offer = Query::find_split_offer(
+            prompt_query.amount_in,
+            route_offers,
+            Query::get_adapters(),
+            parts,
+        );
+
+        sp.stop_with_newline();
+
+        let split_offer = match split_offer {
+            Ok(split_offer) => split_offer,
+            Err(err) => {
+                println!("Error while splitting order: {}", err);
+                return;
+            }
+        };
+
+        let format_amount = |amount: U256, token: &Token| {
+            format!(
+                "{:.4} {}",
+                format_units(amount, token.decimals)
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap(),
+                token.symbol
+            )
+        };
+
+        let format_net = |net_amount_out: Option<U256>| match net_amount_out {
+            Some(net_amount_out) => format_amount(net_amount_out, &prompt_query.token_out),
+            None => "n/a".to_owned(),
+        };
+
+        if split_offer.legs.len() < 2 {
+            println!("Splitting doesn't beat the best single path");
+
+            QueryScreen::format_offer_result(
+                single_offer.to_owned(),
+                prompt_query.token_out.to_owned(),
+                &Query::get_gas_price(),
+            );
+
+            Self::swap_offer(single_offer, prompt_query);
+            return;
+        }
+
+        println!();
+        for (index, leg) in split_offer.legs.iter().enumerate() {
+            println!(
+                "Leg {}: {} -> {} via {} hop(s)",
+                index + 1,
+                format_amount(*leg.amounts.first().unwrap(), &prompt_query.token_in),
+                format_amount(*leg.amounts.last().unwrap(), &prompt_query.token_out),
+                leg.adapters.len()
+            );
+        }
+        println!();
+        println!(
+            "Single path: {} (net {})",
+            format_amount(single_amount_out, &prompt_query.token_out),
+            format_net(single_net_amount_out)
+        );
+        println!(
+            "Split order: {} (net {})",
+            format_amount(split_offer.amount_out, &prompt_query.token_out),
+            format_net(split_offer.net_amount_out)
+        );
+
+        match split_offer.net_amount_out.zip(single_net_amount_out) {
+            Some((split_net, single_net)) if split_net > single_net => println!(
+                "Improvement after gas: {}",
+                style(format_amount(
+                    split_net - single_net,
+                    &prompt_query.token_out
+                ))
+                .green()
+            ),
+            Some(_) => println!(
+                "{}",
+                style("Split order is not better than single path after gas").red()
+            ),
+            None => {}
+        }
+        println!();
+
+        Self::swap_split(split_offer.legs, prompt_query);
+    }
+
+    // @dev one approve covers all legs, every leg gets its own slippage floor
+    fn swap_split(legs: Vec<FormattedOfferWithGas>, prompt_query: QueryPrompt) {
+        let confirm = Confirm::new()
+            .with_prompt(format!("Send {} swap transactions?", legs.len()))
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let current_wallet = match WalletStorage::get_current_wallet() {
+            Some(current_wallet) => current_wallet,
+            None => {
+                println!("No wallet set");
+                return;
+            }
+        };
+
+        if current_wallet.watch_only {
+            Self::print_watch_only_refusal();
+            return;
+        }
+
+        let recipient = match Self::prompt_recipient(current_wallet.address) {
+            Some(recipient) => recipient,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let password: String = Password::new()
+            .with_prompt("Current Wallet password")
+            .interact()
+            .unwrap();
+
+        let wallet =
+            crate::wallet::AccountWallet::decrypt_wallet(current_wallet.name.to_owned(), password);
+
+        if wallet.is_err() {
+            return;
+        }
+
+        let wallet = wallet.expect("Something wrong with wallet");
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let signing_wallet = wallet.wallet();
+
+        let from_to_native = Swap::decide_from_to_native(
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        let is_from_native = matches!(from_to_native, Some(FromToNative::FromNative));
+
+        let token_in_balance = QueryScreen::get_token_in_balance(
+            prompt_query.token_in.to_owned(),
+            current_wallet.to_owned(),
+        )
+        .unwrap_or_default();
+
+        if token_in_balance < prompt_query.amount_in {
+            println!(
+                "Balance of {} less than amount you want to swap",
+                prompt_query.token_in.symbol
+            );
+            return;
+        }
+
+        let amount_out = legs.iter().fold(U256::zero(), |total, leg| {
+            total + leg.amounts.last().unwrap()
+        });
+
+        let transfer_fee = match Self::check_transfer_fees(
+            &prompt_query,
+            current_wallet.address,
+            amount_out,
+            current_network.clone(),
+        ) {
+            Some(transfer_fee) => transfer_fee,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let yak_router_address = current_network
+            .yak_router
+            .as_ref()
+            .unwrap()
+            .parse::<H160>()
+            .unwrap();
+
+        if !is_from_native {
+            let allowance = Token::get_allowance(
+                current_wallet.address,
+                yak_router_address,
+                prompt_query.token_in.address.parse::<H160>().unwrap(),
+                current_network.clone(),
+            );
+
+            if allowance < prompt_query.amount_in
+                && !Self::approve_router(
+                    &prompt_query,
+                    allowance,
+                    yak_router_address,
+                    signing_wallet,
+                    current_network.clone(),
+                )
+            {
+                return;
+            }
+        }
+
+        let legs_count = legs.len();
+
+        for (index, leg) in legs.into_iter().enumerate() {
+            let mut sp = Spinner::new(
+                Spinners::Aesthetic,
+                format!("Swapping leg {}/{}...", index + 1, legs_count),
+            );
+
+            let mut trade = Trade {
+                amount_in: *leg.amounts.first().unwrap(),
+                amount_out: *leg.amounts.last().unwrap(),
+                path: leg.path,
+                adapters: leg.adapters,
+            };
+
+            trade.handle_slippage(transfer_fee);
+
+            let swap_receipt = Swap::swap_no_split(
+                trade,
+                recipient,
+                from_to_native,
+                signing_wallet,
+                current_network.clone(),
+            );
+
+            sp.stop_with_newline();
+
+            match swap_receipt {
+                Some(swap_receipt) if swap_receipt.status != Some(U64::zero()) => println!(
+                    "Leg {} tx url: {explorer}/tx/{:?}",
+                    index + 1,
+                    swap_receipt.transaction_hash,
+                    explorer = current_network.explorer_url
+                ),
+                _ => {
+                    println!(
+                        "Leg {} failed, remaining {} leg(s) are not sent",
+                        index + 1,
+                        legs_count - index - 1
+                    );
+                    return;
+                }
+            }
+        }
+
+        println!("{}", style("Hooray, split order swapped!").green());
+    }
+
+    // @dev confirm, approve or permit and send swap of already selected offer
+    fn swap_offer(formatted_offer: FormattedOfferWithGas, prompt_query: QueryPrompt) {
         let confirm = Confirm::new()
             .with_prompt("Do you want to continue?")
             .default(true)
@@ -178,6 +659,51 @@ impl SwapScreen {
         drop(db_instance);
 
         if let Some(current_wallet) = current_wallet {
+            if current_wallet.watch_only {
+                Self::print_watch_only_refusal();
+
+                let export_items = [
+                    "Export unsigned transaction",
+                    "Export Safe Transaction Builder batch",
+                    "Cancel",
+                ];
+
+                let export_selection = Select::with_theme(&ColorfulTheme::default())
+                    .items(&export_items)
+                    .with_prompt("What do you want to do instead?")
+                    .default(0)
+                    .interact_on_opt(&Term::stderr())
+                    .unwrap();
+
+                match export_selection {
+                    Some(0) => match Self::prompt_recipient(current_wallet.address) {
+                        Some(recipient) => Self::export_unsigned_swap(
+                            formatted_offer,
+                            &prompt_query,
+                            current_wallet.address,
+                            recipient,
+                        ),
+                        None => println!("Ok, next time"),
+                    },
+                    Some(1) => Self::export_safe_batch(
+                        formatted_offer,
+                        &prompt_query,
+                        current_wallet.address,
+                    ),
+                    _ => println!("Ok, next time"),
+                }
+
+                return;
+            }
+
+            let recipient = match Self::prompt_recipient(current_wallet.address) {
+                Some(recipient) => recipient,
+                None => {
+                    println!("Ok, next time");
+                    return;
+                }
+            };
+
             let password: String = Password::new()
                 .with_prompt("Current Wallet password")
                 .interact()
@@ -242,6 +768,19 @@ impl SwapScreen {
                 return;
             }
 
+            let transfer_fee = match Self::check_transfer_fees(
+                &prompt_query,
+                current_wallet.address,
+                *formatted_offer.amounts.last().unwrap(),
+                current_network.clone(),
+            ) {
+                Some(transfer_fee) => transfer_fee,
+                None => {
+                    println!("Ok, next time");
+                    return;
+                }
+            };
+
             let yak_router_address = current_network
                 .yak_router
                 .as_ref()
@@ -249,9 +788,6 @@ impl SwapScreen {
                 .parse::<H160>()
                 .unwrap();
 
-            let has_permit =
-                Query::has_permit(prompt_query.token_in.address.parse::<H160>().unwrap());
-
             // @dev check allowance
             let mut allowance = U256::zero();
             if !is_from_native {
@@ -263,71 +799,103 @@ impl SwapScreen {
                 );
             }
 
-            let need_permit = has_permit && allowance < prompt_query.amount_in;
+            // @dev permit is used only when its domain matches token DOMAIN_SEPARATOR, approve otherwise
+            let token_permit = if !is_from_native && allowance < prompt_query.amount_in {
+                let mut sp = Spinner::new(Spinners::Aesthetic, "Checking permit support...".into());
 
-            // @dev approve tokens
-            if !is_from_native && !has_permit && allowance < prompt_query.amount_in {
-                println!(
-                    "Allowance of {} less than amount you want to swap",
-                    prompt_query.token_in.symbol
+                let token_permit = Permit::get_permit(
+                    prompt_query.token_in.address.parse::<H160>().unwrap(),
+                    current_wallet.address,
+                    current_network.clone(),
                 );
 
-                let confirm = Confirm::new()
-                    .with_prompt("Do you want to approve tokens to spend?")
-                    .default(true)
-                    .interact()
-                    .unwrap();
+                sp.stop_with_newline();
 
-                if !confirm {
-                    println!("Ok, next time");
-                    return;
+                match &token_permit {
+                    Some(TokenPermit {
+                        kind: PermitKind::Eip2612,
+                        domain,
+                    }) => println!("Using permit signature instead of approve, domain {}", domain),
+                    Some(TokenPermit {
+                        kind: PermitKind::Dai,
+                        domain,
+                    }) => println!(
+                        "Using DAI-style permit, domain {}: permit, swap and permit revoke are sent as separate transactions",
+                        domain
+                    ),
+                    None => {}
                 }
 
-                let mut sp = Spinner::new(Spinners::Aesthetic, "Approving...".into());
+                token_permit.and_then(Self::confirm_permit_deadline)
+            } else {
+                None
+            };
 
-                let approve_receipt = crate::token::Token::approve(
+            // @dev approve tokens
+            if !is_from_native
+                && token_permit.is_none()
+                && allowance < prompt_query.amount_in
+                && !Self::approve_router(
+                    &prompt_query,
+                    allowance,
                     yak_router_address,
-                    U256::MAX,
-                    prompt_query.token_in.address.parse::<H160>().unwrap(),
                     signing_wallet,
                     current_network.clone(),
-                );
-
-                sp.stop_with_newline();
-
-                if let Some(approve_receipt) = approve_receipt {
-                    println!("TX Hash: {}", approve_receipt.transaction_hash);
-                } else {
-                    println!("Error when getting tx hash on approve");
-                }
+                )
+            {
+                return;
             }
 
             // spinner & swap
             let mut sp = Spinner::new(Spinners::Aesthetic, "Swapping...".into());
 
-            let trade = Trade {
+            let mut trade = Trade {
                 amount_in: *formatted_offer.amounts.first().unwrap(),
                 amount_out: *formatted_offer.amounts.last().unwrap(),
                 path: formatted_offer.path,
                 adapters: formatted_offer.adapters,
             };
 
-            let swap_receipt = if !need_permit {
-                Swap::swap_no_split(
+            trade.handle_slippage(transfer_fee);
+
+            let swap_receipt = match token_permit {
+                None => Swap::swap_no_split(
                     trade,
-                    current_wallet.address,
+                    recipient,
                     from_to_native,
                     signing_wallet,
                     current_network.clone(),
-                )
-            } else {
-                Swap::swap_no_split_with_permit(
+                ),
+                Some((
+                    TokenPermit {
+                        kind: PermitKind::Dai,
+                        domain,
+                    },
+                    deadline,
+                )) => Swap::swap_no_split_with_dai_permit(
                     trade,
-                    current_wallet.address,
+                    &domain,
+                    deadline,
+                    recipient,
                     from_to_native,
                     signing_wallet,
                     current_network.clone(),
-                )
+                ),
+                Some((
+                    TokenPermit {
+                        kind: PermitKind::Eip2612,
+                        domain,
+                    },
+                    deadline,
+                )) => Swap::swap_no_split_with_permit(
+                    trade,
+                    &domain,
+                    deadline,
+                    recipient,
+                    from_to_native,
+                    signing_wallet,
+                    current_network.clone(),
+                ),
             };
 
             sp.stop_with_newline();
@@ -352,8 +920,21 @@ impl SwapScreen {
         let current_wallet = storage::WalletStorage::get_current_wallet();
 
         if let Some(current_wallet) = current_wallet {
+            if current_wallet.watch_only {
+                Self::print_watch_only_refusal();
+                return;
+            }
+
             let current_network = Arc::new(Network::get_current_network());
 
+            let wrapped_native = match Token::get_native_wrapped(current_network.clone()) {
+                Ok(wrapped_native) => wrapped_native,
+                Err(err) => {
+                    println!("{}", style(err).red());
+                    return;
+                }
+            };
+
             let amount_input = Input::<String>::new()
                 .with_prompt("Amount to Wrap")
                 .interact_text()
@@ -386,7 +967,12 @@ impl SwapScreen {
 
             let mut sp = Spinner::new(Spinners::Aesthetic, "Wrapping tokens...".into());
 
-            let receipt = Swap::wrap_native(amount_in, signing_wallet, current_network.clone());
+            let receipt = Swap::wrap_native(
+                amount_in,
+                wrapped_native,
+                signing_wallet,
+                current_network.clone(),
+            );
 
             sp.stop_with_newline();
 
@@ -410,7 +996,20 @@ impl SwapScreen {
         let current_wallet = storage::WalletStorage::get_current_wallet();
 
         if let Some(current_wallet) = current_wallet {
-            let current_network = Arc::new(Network::get_current_network());
+            if current_wallet.watch_only {
+                Self::print_watch_only_refusal();
+                return;
+            }
+
+            let current_network = Arc::new(Network::get_current_network());
+
+            let wrapped_native = match Token::get_native_wrapped(current_network.clone()) {
+                Ok(wrapped_native) => wrapped_native,
+                Err(err) => {
+                    println!("{}", style(err).red());
+                    return;
+                }
+            };
 
             let amount_input = Input::<String>::new()
                 .with_prompt("Amount to Unwrap")
@@ -421,11 +1020,9 @@ impl SwapScreen {
 
             let mut sp = Spinner::new(Spinners::Aesthetic, "Getting current balance...".into());
 
-            let wrapped_token = Token::get_native_wrapped(current_network.chain_id);
-
             let token_balance = Token::get_token_balance(
                 current_wallet.address,
-                wrapped_token,
+                wrapped_native,
                 current_network.clone(),
             );
 
@@ -436,6 +1033,14 @@ impl SwapScreen {
                 return;
             }
 
+            let recipient = match Self::prompt_recipient(current_wallet.address) {
+                Some(recipient) => recipient,
+                None => {
+                    println!("Ok, next time");
+                    return;
+                }
+            };
+
             let password: String = Password::new()
                 .with_prompt("Current Wallet password")
                 .interact()
@@ -449,23 +1054,835 @@ impl SwapScreen {
 
             let mut sp = Spinner::new(Spinners::Aesthetic, "Unwrapping tokens...".into());
 
-            let receipt = Swap::unwrap_native(amount_in, signing_wallet, current_network.clone());
+            let receipt = Swap::unwrap_native(
+                amount_in,
+                wrapped_native,
+                recipient,
+                signing_wallet,
+                current_network.clone(),
+            );
 
             sp.stop_with_newline();
 
-            if let Some(receipt) = receipt {
-                println!("{}", style("Hooray, successful unwrap!").green());
+            match receipt {
+                Ok(receipt) => {
+                    println!("{}", style("Hooray, successful unwrap!").green());
+                    let tx_url = format!(
+                        "{explorer}/tx/{:?}",
+                        receipt.transaction_hash,
+                        explorer = current_network.explorer_url
+                    );
+                    println!("tx url: {}", tx_url);
+                }
+                Err(err) => println!("{}", err),
+            }
+        } else {
+            println!("No current wallet set");
+        }
+    }
+
+    // @dev returns None when user doesn't confirm a recipient different from signer
+    fn prompt_recipient(signer: H160) -> Option<H160> {
+        let recipient_items = [
+            "Current account",
+            "Another stored account",
+            "Address book",
+            "Enter address",
+        ];
+
+        let recipient_selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&recipient_items)
+            .with_prompt("Recipient")
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let recipient = match recipient_selection {
+            Some(1) => {
+                let wallets: Vec<WalletStorage> = storage::WalletStorage::get_wallets()
+                    .into_iter()
+                    .filter(|wallet| wallet.address != signer)
+                    .collect();
+
+                if wallets.is_empty() {
+                    println!("No other stored accounts");
+                    return None;
+                }
+
+                let wallet_selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+                    .items(&wallets)
+                    .with_prompt("Recipient account")
+                    .default(0)
+                    .interact_on_opt(&Term::stderr())
+                    .unwrap();
+
+                wallets[wallet_selection?].address
+            }
+            Some(2) => AddressBookScreen::select_entry()?.address,
+            Some(3) => {
+                let address = Input::<String>::new()
+                    .with_prompt("Recipient address")
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        AccountWallet::parse_address(input).map(|_| ())
+                    })
+                    .interact_text()
+                    .unwrap();
+
+                let address = AccountWallet::parse_address(&address).unwrap();
+
+                let current_network = Network::get_current_network();
+
+                if AddressBook::get_label(address, current_network.chain_id).is_none()
+                    && Confirm::new()
+                        .with_prompt("Save this address to address book?")
+                        .default(false)
+                        .interact()
+                        .unwrap()
+                {
+                    let label = Input::<String>::new()
+                        .with_prompt("Label")
+                        .interact_text()
+                        .unwrap();
+
+                    AddressBook::save_entry(AddressBookEntry {
+                        label,
+                        address,
+                        chain_id: Some(current_network.chain_id),
+                    });
+                }
+
+                address
+            }
+            Some(_) => signer,
+            None => return None,
+        };
+
+        if recipient == signer {
+            return Some(recipient);
+        }
+
+        println!();
+        println!(
+            "{}",
+            style("!!! Recipient differs from signing account !!!")
+                .red()
+                .bold()
+        );
+        let chain_id = Network::get_current_network().chain_id;
+
+        println!(
+            "Signer:    {}",
+            AddressBook::format_address(signer, chain_id)
+        );
+        println!(
+            "Recipient: {}",
+            style(AddressBook::format_address(recipient, chain_id))
+                .yellow()
+                .bold()
+        );
+        println!();
+
+        let confirm = Confirm::new()
+            .with_prompt("Send output to this recipient?")
+            .default(false)
+            .interact()
+            .unwrap();
+
+        if confirm {
+            Some(recipient)
+        } else {
+            None
+        }
+    }
+
+    // @dev simulates transfers from holder, token out only if holder already has some of it.
+    // @dev returns extra slippage for fee-on-transfer and rebasing tokens, None if user cancelled
+    fn check_transfer_fees(
+        prompt_query: &QueryPrompt,
+        holder: H160,
+        amount_out: U256,
+        current_network: Arc<Network>,
+    ) -> Option<u32> {
+        let mut transfer_fee = 0;
+
+        let tokens = [
+            (&prompt_query.token_in, prompt_query.amount_in),
+            (&prompt_query.token_out, amount_out),
+        ];
+
+        for (index, (token, amount)) in tokens.into_iter().enumerate() {
+            let token_address = token.address.parse::<H160>().unwrap();
+
+            if Token::is_native(token_address) {
+                continue;
+            }
+
+            let mut amount = amount;
+
+            if index == 1 {
+                let balance =
+                    Token::get_token_balance(holder, token_address, current_network.clone());
+
+                if balance.is_zero() {
+                    println!(
+                        "Can't check {} for transfer fee, account has no balance to simulate with",
+                        token.symbol
+                    );
+                    continue;
+                }
+
+                amount = amount.min(balance);
+            }
+
+            let mut sp = Spinner::new(
+                Spinners::Aesthetic,
+                format!("Simulating {} transfer...", token.symbol),
+            );
+
+            let simulation =
+                Token::simulate_transfer(token_address, holder, amount, current_network.clone());
+
+            sp.stop_with_newline();
+
+            match simulation {
+                Ok(simulation) if simulation.is_standard() => {}
+                Ok(simulation) => {
+                    let fee = simulation.fee();
+
+                    println!(
+                        "{}",
+                        style(format!(
+                            "Warning: {} is a fee-on-transfer or rebasing token, transfer of {} sent {} and received {}",
+                            token.symbol,
+                            format_units(simulation.amount, token.decimals).unwrap(),
+                            format_units(simulation.sent, token.decimals).unwrap(),
+                            format_units(simulation.received, token.decimals).unwrap()
+                        ))
+                        .red()
+                    );
+
+                    transfer_fee += fee;
+                }
+                Err(err) => println!("Can't simulate {} transfer: {}", token.symbol, err),
+            }
+        }
+
+        if transfer_fee == 0 {
+            return Some(0);
+        }
+
+        if transfer_fee > Token::MAX_TRANSFER_FEE {
+            println!(
+                "{}",
+                style(format!(
+                    "Transfer fee {:.1}% is above {:.1}% limit, swap is refused",
+                    transfer_fee as f64 / 10.0,
+                    Token::MAX_TRANSFER_FEE as f64 / 10.0
+                ))
+                .red()
+            );
+            return None;
+        }
+
+        let amount_out_min = Trade::apply_slippage(
+            Trade::apply_slippage(amount_out, transfer_fee),
+            Settings::get_slippage(),
+        );
+
+        println!(
+            "Slippage floor lowered by {:.1}% on top of {:.1}% slippage tolerance, minimum received {} {}",
+            transfer_fee as f64 / 10.0,
+            Settings::get_slippage() as f64 / 10.0,
+            format_units(amount_out_min, prompt_query.token_out.decimals).unwrap(),
+            prompt_query.token_out.symbol
+        );
+
+        let confirm = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to continue with lowered slippage floor?")
+            .default(false)
+            .interact()
+            .unwrap();
+
+        if confirm {
+            Some(transfer_fee)
+        } else {
+            None
+        }
+    }
+
+    // @dev permit deadline is latest block timestamp + setting, approve is used if it's declined
+    fn confirm_permit_deadline(token_permit: TokenPermit) -> Option<(TokenPermit, U256)> {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let deadline = match Permit::get_deadline(current_network) {
+            Ok(deadline) => deadline,
+            Err(err) => {
+                println!("Error while getting permit deadline: {}", err);
+                return None;
+            }
+        };
+
+        println!(
+            "Permit deadline: block timestamp {} ({} minutes from latest block)",
+            deadline,
+            Settings::get_permit_deadline()
+        );
+
+        let confirm = Confirm::new()
+            .with_prompt("Sign permit with this deadline? (approve is used otherwise)")
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if confirm {
+            Some((token_permit, deadline))
+        } else {
+            None
+        }
+    }
+
+    // @dev false when user cancelled or allowance reset failed, swap shouldn't be sent then
+    pub fn approve_router(
+        prompt_query: &QueryPrompt,
+        allowance: U256,
+        yak_router_address: H160,
+        signing_wallet: &Wallet<SigningKey>,
+        current_network: Arc<Network>,
+    ) -> bool {
+        println!(
+            "Allowance of {} less than amount you want to swap",
+            prompt_query.token_in.symbol
+        );
+
+        let approve_amount = match Self::select_approve_amount(prompt_query) {
+            Some(approve_amount) => approve_amount,
+            None => {
+                println!("Ok, next time");
+                return false;
+            }
+        };
+
+        let confirm = Confirm::new()
+            .with_prompt("Send approve?")
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return false;
+        }
+
+        if !allowance.is_zero() && Self::confirm_reset_allowance(prompt_query, allowance) {
+            let mut sp = Spinner::new(Spinners::Aesthetic, "Resetting allowance to 0...".into());
+
+            let reset_receipt = crate::token::Token::approve(
+                yak_router_address,
+                U256::zero(),
+                prompt_query.token_in.address.parse::<H160>().unwrap(),
+                signing_wallet,
+                current_network.clone(),
+            );
+
+            sp.stop_with_newline();
+
+            if let Some(reset_receipt) = reset_receipt {
+                println!("TX Hash: {}", reset_receipt.transaction_hash);
+            } else {
+                println!("Error when getting tx hash on allowance reset");
+                return false;
+            }
+        }
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Approving...".into());
+
+        let approve_receipt = crate::token::Token::approve(
+            yak_router_address,
+            approve_amount,
+            prompt_query.token_in.address.parse::<H160>().unwrap(),
+            signing_wallet,
+            current_network,
+        );
+
+        sp.stop_with_newline();
+
+        if let Some(approve_receipt) = approve_receipt {
+            println!("TX Hash: {}", approve_receipt.transaction_hash);
+        } else {
+            println!("Error when getting tx hash on approve");
+        }
+
+        true
+    }
+
+    // @dev tokens like USDT revert when non-zero allowance is changed to another non-zero value
+    fn confirm_reset_allowance(prompt_query: &QueryPrompt, allowance: U256) -> bool {
+        Confirm::new()
+            .with_prompt(format!(
+                "Current allowance is {} {}, reset it to 0 first? (required by tokens like USDT)",
+                format_units(allowance, prompt_query.token_in.decimals).unwrap(),
+                prompt_query.token_in.symbol
+            ))
+            .default(false)
+            .interact()
+            .unwrap()
+    }
+
+    // @dev approval policy is chosen per swap, setting is the default. None if user cancelled
+    fn select_approve_amount(prompt_query: &QueryPrompt) -> Option<U256> {
+        let approval_policy =
+            SettingsScreen::select_approval_policy(&Settings::get_approval_policy())?;
+
+        let approve_amount = approval_policy.amount(prompt_query.amount_in);
+
+        println!(
+            "Approve: {} {} ({})",
+            Self::format_approve_amount(approve_amount, prompt_query),
+            prompt_query.token_in.symbol,
+            approval_policy
+        );
+
+        Some(approve_amount)
+    }
+
+    fn format_approve_amount(amount: U256, prompt_query: &QueryPrompt) -> String {
+        if amount == U256::MAX {
+            "unlimited".to_owned()
+        } else {
+            format_units(amount, prompt_query.token_in.decimals).unwrap()
+        }
+    }
+
+    fn print_watch_only_refusal() {
+        println!(
+            "{}",
+            style("Current account is watch-only and can't sign transactions").red()
+        );
+    }
+
+    fn export_unsigned_swap(
+        formatted_offer: FormattedOfferWithGas,
+        prompt_query: &QueryPrompt,
+        from: H160,
+        to: H160,
+    ) {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let token_in_address = prompt_query.token_in.address.parse::<H160>().unwrap();
+
+        let from_to_native = Swap::decide_from_to_native(
+            token_in_address,
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        // @dev swap gas can't be estimated until router is allowed to spend tokens
+        if !matches!(from_to_native, Some(FromToNative::FromNative)) {
+            let yak_router_address = current_network
+                .yak_router
+                .as_ref()
+                .unwrap()
+                .parse::<H160>()
+                .unwrap();
+
+            let allowance = Token::get_allowance(
+                from,
+                yak_router_address,
+                token_in_address,
+                current_network.clone(),
+            );
+
+            if allowance < prompt_query.amount_in {
+                println!(
+                    "Allowance of {} less than amount you want to swap, exporting approve first",
+                    prompt_query.token_in.symbol
+                );
+
+                let approve_amount = match Self::select_approve_amount(prompt_query) {
+                    Some(approve_amount) => approve_amount,
+                    None => {
+                        println!("Ok, next time");
+                        return;
+                    }
+                };
+
+                // @dev USDT-like tokens need reset first, it's exported instead of approve then
+                let (approve_amount, default_path) = if !allowance.is_zero()
+                    && Self::confirm_reset_allowance(prompt_query, allowance)
+                {
+                    (U256::zero(), "unsigned-reset-approve.json")
+                } else {
+                    (approve_amount, "unsigned-approve.json")
+                };
+
+                let approve_tx = Token::build_approve_tx(
+                    yak_router_address,
+                    approve_amount,
+                    token_in_address,
+                    current_network.clone(),
+                );
+
+                Self::export_unsigned(approve_tx, from, current_network, default_path);
+
+                println!("Sign and broadcast approve, then export the swap again");
+
+                return;
+            }
+        }
+
+        let transfer_fee = match Self::check_transfer_fees(
+            prompt_query,
+            from,
+            *formatted_offer.amounts.last().unwrap(),
+            current_network.clone(),
+        ) {
+            Some(transfer_fee) => transfer_fee,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let mut trade = Trade {
+            amount_in: *formatted_offer.amounts.first().unwrap(),
+            amount_out: *formatted_offer.amounts.last().unwrap(),
+            path: formatted_offer.path,
+            adapters: formatted_offer.adapters,
+        };
+
+        trade.handle_slippage(transfer_fee);
+
+        let swap_tx =
+            Swap::build_swap_no_split_tx(trade, to, from_to_native, current_network.clone());
+
+        Self::export_unsigned(swap_tx, from, current_network, "unsigned-swap.json");
+    }
+
+    fn export_unsigned(
+        tx: ethers::types::transaction::eip2718::TypedTransaction,
+        from: H160,
+        current_network: Arc<Network>,
+        default_path: &str,
+    ) {
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Populating transaction...".into());
+
+        let unsigned_tx = Offline::populate(tx, from, current_network);
+
+        sp.stop_with_newline();
+
+        match unsigned_tx {
+            Ok(unsigned_tx) => {
+                Self::print_unsigned(&unsigned_tx);
+
+                let path = Input::<String>::new()
+                    .with_prompt("Export to file")
+                    .default(default_path.to_owned())
+                    .interact_text()
+                    .unwrap();
+
+                match Offline::export_unsigned(&unsigned_tx, &path) {
+                    Ok(_) => println!(
+                        "{} {}",
+                        style("Unsigned transaction exported to").green(),
+                        path
+                    ),
+                    Err(err) => println!("Error while exporting transaction: {}", err),
+                }
+            }
+            Err(err) => println!("Error while populating transaction: {}", err),
+        }
+    }
+
+    // @dev Safe is both sender and recipient, owners import the batch to Transaction Builder
+    fn export_safe_batch(
+        formatted_offer: FormattedOfferWithGas,
+        prompt_query: &QueryPrompt,
+        safe: H160,
+    ) {
+        let current_network = Arc::new(Network::get_current_network());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Getting Safe info...".into());
+
+        let safe_info = Safe::get_info(safe, current_network.clone());
+
+        sp.stop_with_newline();
+
+        let safe_info = match safe_info {
+            Ok(safe_info) => safe_info,
+            Err(err) => {
+                println!("{:?} doesn't look like a Safe: {}", safe, err);
+                return;
+            }
+        };
+
+        let token_in_address = prompt_query.token_in.address.parse::<H160>().unwrap();
+
+        let from_to_native = Swap::decide_from_to_native(
+            token_in_address,
+            prompt_query.token_out.address.parse::<H160>().unwrap(),
+        );
+
+        let mut transactions: Vec<SafeTransaction> = vec![];
+
+        if !matches!(from_to_native, Some(FromToNative::FromNative)) {
+            let yak_router_address = current_network
+                .yak_router
+                .as_ref()
+                .unwrap()
+                .parse::<H160>()
+                .unwrap();
+
+            let allowance = Token::get_allowance(
+                safe,
+                yak_router_address,
+                token_in_address,
+                current_network.clone(),
+            );
+
+            if allowance < prompt_query.amount_in {
+                let approve_amount = match Self::select_approve_amount(prompt_query) {
+                    Some(approve_amount) => approve_amount,
+                    None => {
+                        println!("Ok, next time");
+                        return;
+                    }
+                };
+
+                if !allowance.is_zero() && Self::confirm_reset_allowance(prompt_query, allowance) {
+                    let reset_tx = Token::build_approve_tx(
+                        yak_router_address,
+                        U256::zero(),
+                        token_in_address,
+                        current_network.clone(),
+                    );
+
+                    transactions.push(SafeTransaction::from(reset_tx));
+                }
+
+                let approve_tx = Token::build_approve_tx(
+                    yak_router_address,
+                    approve_amount,
+                    token_in_address,
+                    current_network.clone(),
+                );
+
+                transactions.push(SafeTransaction::from(approve_tx));
+            }
+        }
+
+        let transfer_fee = match Self::check_transfer_fees(
+            prompt_query,
+            safe,
+            *formatted_offer.amounts.last().unwrap(),
+            current_network.clone(),
+        ) {
+            Some(transfer_fee) => transfer_fee,
+            None => {
+                println!("Ok, next time");
+                return;
+            }
+        };
+
+        let mut trade = Trade {
+            amount_in: *formatted_offer.amounts.first().unwrap(),
+            amount_out: *formatted_offer.amounts.last().unwrap(),
+            path: formatted_offer.path,
+            adapters: formatted_offer.adapters,
+        };
+
+        trade.handle_slippage(transfer_fee);
+
+        let swap_tx =
+            Swap::build_swap_no_split_tx(trade, safe, from_to_native, current_network.clone());
+
+        transactions.push(SafeTransaction::from(swap_tx));
+
+        let description = format!(
+            "Swap {} {} to {}",
+            format_units(prompt_query.amount_in, prompt_query.token_in.decimals).unwrap(),
+            prompt_query.token_in.symbol,
+            prompt_query.token_out.symbol
+        );
+
+        let batch = Safe::build_batch(safe, current_network.chain_id, &description, &transactions);
+
+        let safe_tx_hash = Safe::safe_tx_hash(&safe_info, &transactions);
+
+        println!();
+        println!(
+            "Safe: {:?} (version {}, threshold {} of {})",
+            safe,
+            safe_info.version,
+            safe_info.threshold,
+            safe_info.owners.len()
+        );
+        println!("Transactions in batch: {}", transactions.len());
+        println!("Safe nonce: {}", safe_info.nonce);
+        match safe_tx_hash {
+            Some(safe_tx_hash) => println!(
+                "Safe tx hash: {}",
+                style(format!("{:?}", safe_tx_hash)).green()
+            ),
+            None => println!(
+                "Safe tx hash: {}",
+                style("unknown, no MultiSendCallOnly for this Safe version on this network")
+                    .yellow()
+            ),
+        }
+        println!();
+
+        let path = Input::<String>::new()
+            .with_prompt("Export to file")
+            .default("safe-batch.json".to_owned())
+            .interact_text()
+            .unwrap();
+
+        match Safe::export_batch(&batch, &path) {
+            Ok(_) => println!("{} {}", style("Safe batch exported to").green(), path),
+            Err(err) => println!("Error while exporting batch: {}", err),
+        }
+    }
+
+    fn print_unsigned(tx: &UnsignedTransaction) {
+        let current_network = Network::get_current_network();
+
+        println!();
+        println!("From: {:?}", tx.from);
+        println!("To: {:?}", tx.to);
+        println!(
+            "Value: {} {}",
+            format_units(tx.value, "ether").unwrap(),
+            current_network.currency_symbol
+        );
+        println!("Nonce: {}", tx.nonce);
+        println!("Gas limit: {}", tx.gas);
+        if let Some(gas_price) = tx.gas_price {
+            println!(
+                "Gas price: {} gwei",
+                format_units(gas_price, "gwei").unwrap()
+            );
+        }
+        if let Some(max_fee_per_gas) = tx.max_fee_per_gas {
+            println!(
+                "Max fee per gas: {} gwei",
+                format_units(max_fee_per_gas, "gwei").unwrap()
+            );
+        }
+        println!("Chain id: {}", tx.chain_id);
+        println!();
+    }
+
+    fn sign_offline() {
+        let path = Input::<String>::new()
+            .with_prompt("Unsigned transaction file")
+            .default("unsigned-swap.json".to_owned())
+            .interact_text()
+            .unwrap();
+
+        let unsigned_tx = match Offline::load_unsigned(&path) {
+            Ok(unsigned_tx) => unsigned_tx,
+            Err(err) => {
+                println!("Error while reading transaction: {}", err);
+                return;
+            }
+        };
+
+        Self::print_unsigned(&unsigned_tx);
+
+        let signer_wallet = storage::WalletStorage::get_wallets()
+            .into_iter()
+            .find(|wallet| !wallet.watch_only && wallet.address == unsigned_tx.from);
+
+        let signer_wallet = match signer_wallet {
+            Some(signer_wallet) => signer_wallet,
+            None => {
+                println!("No stored key for {:?}", unsigned_tx.from);
+                return;
+            }
+        };
+
+        let confirm = Confirm::new()
+            .with_prompt(format!("Sign with {}?", signer_wallet.name))
+            .default(true)
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return;
+        }
+
+        let password: String = Password::new()
+            .with_prompt("Wallet password")
+            .interact()
+            .unwrap();
+
+        let wallet = crate::wallet::AccountWallet::decrypt_wallet(signer_wallet.name, password);
+
+        if wallet.is_err() {
+            return;
+        }
+
+        let wallet = wallet.expect("Something wrong with wallet");
+
+        match Offline::sign(&unsigned_tx, wallet.wallet()) {
+            Ok(signed_tx) => {
+                let path = Input::<String>::new()
+                    .with_prompt("Export to file")
+                    .default("signed-tx.json".to_owned())
+                    .interact_text()
+                    .unwrap();
+
+                match Offline::export_signed(&signed_tx, &path) {
+                    Ok(_) => {
+                        println!(
+                            "{} {}",
+                            style("Signed transaction exported to").green(),
+                            path
+                        );
+                        println!("TX Hash: {:?}", signed_tx.hash);
+                    }
+                    Err(err) => println!("Error while exporting transaction: {}", err),
+                }
+            }
+            Err(err) => println!("Error while signing transaction: {}", err),
+        }
+    }
+
+    fn broadcast() {
+        let path_or_raw = Input::<String>::new()
+            .with_prompt("Signed transaction file or raw transaction hex")
+            .default("signed-tx.json".to_owned())
+            .interact_text()
+            .unwrap();
+
+        let raw_transaction = match Offline::load_signed(&path_or_raw) {
+            Ok(raw_transaction) => raw_transaction,
+            Err(err) => {
+                println!("Error while reading transaction: {}", err);
+                return;
+            }
+        };
+
+        let current_network = Arc::new(Network::get_current_network());
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Broadcasting...".into());
+
+        let receipt = Offline::broadcast(raw_transaction, current_network.clone());
+
+        sp.stop_with_newline();
+
+        match receipt {
+            Ok(Some(receipt)) => {
+                println!("{}", style("Hooray, transaction landed!").green());
                 let tx_url = format!(
                     "{explorer}/tx/{:?}",
                     receipt.transaction_hash,
                     explorer = current_network.explorer_url
                 );
                 println!("tx url: {}", tx_url);
-            } else {
-                println!("Error when getting tx hash on unwrap");
             }
-        } else {
-            println!("No current wallet set");
+            Ok(None) => println!("Error when getting tx receipt on broadcast"),
+            Err(err) => println!("Error while broadcasting transaction: {}", err),
         }
     }
 }
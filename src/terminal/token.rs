@@ -1,8 +1,20 @@
-use crate::{token::token_storage::TokenStorage, token::Token, Terminal};
+use std::sync::Arc;
+
+use crate::{
+    network::Network,
+    token::list::{TokenList, TokenListSource},
+    token::token_storage::TokenStorage,
+    token::Token,
+    wallet::AccountWallet,
+    Terminal,
+};
 use console::{style, Term};
-use dialoguer::{theme::ColorfulTheme, Input, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
+use spinners::{Spinner, Spinners};
+
+use super::allowance::AllowanceScreen;
 
 pub struct TokenScreen {}
 
@@ -10,12 +22,29 @@ pub struct TokenScreen {}
 enum TokenTopics {
     Add,
     Remove,
+    Lists,
+    Allowances,
+    Back,
+}
+
+#[derive(FromPrimitive)]
+enum TokenListTopics {
+    Show,
+    Add,
+    Enable,
+    Remove,
     Back,
 }
 
 impl TokenScreen {
     pub fn render() {
-        let topics = ["1. Add token", "2. Remove token", "<- Go back"];
+        let topics = [
+            "1. Add token",
+            "2. Remove token",
+            "3. Token lists",
+            "4. Allowances",
+            "<- Go back",
+        ];
 
         let selection = Select::with_theme(&ColorfulTheme::default())
             .items(&topics)
@@ -26,9 +55,9 @@ impl TokenScreen {
         match selection {
             Some(index) => match FromPrimitive::from_usize(index) {
                 Some(TokenTopics::Add) => {
-                    Self::add_token();
-
-                    println!("{}", style("Token successfully added!").green());
+                    if Self::add_token().is_some() {
+                        println!("{}", style("Token successfully added!").green());
+                    }
 
                     Terminal::render();
                 }
@@ -37,6 +66,12 @@ impl TokenScreen {
 
                     Terminal::render();
                 }
+                Some(TokenTopics::Lists) => {
+                    Self::render_lists();
+                }
+                Some(TokenTopics::Allowances) => {
+                    AllowanceScreen::render();
+                }
                 Some(TokenTopics::Back) => {
                     Terminal::render();
                 }
@@ -46,32 +81,308 @@ impl TokenScreen {
         }
     }
 
-    fn add_token() -> Token {
-        let name: String = Input::new()
-            .with_prompt("Token Name")
-            .interact_text()
+    fn render_lists() {
+        let topics = [
+            "1. Show token lists",
+            "2. Add token list (URL or file)",
+            "3. Enable/disable token lists",
+            "4. Remove token list",
+            "<- Go back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&topics)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
             .unwrap();
-        let symbol: String = Input::new()
-            .with_prompt("Token Symbol")
+
+        match selection {
+            Some(index) => match FromPrimitive::from_usize(index) {
+                Some(TokenListTopics::Show) => {
+                    Self::show_lists();
+                    Self::render_lists();
+                }
+                Some(TokenListTopics::Add) => {
+                    Self::add_list();
+                    Terminal::render();
+                }
+                Some(TokenListTopics::Enable) => {
+                    Self::enable_lists();
+                    Terminal::render();
+                }
+                Some(TokenListTopics::Remove) => {
+                    Self::remove_list();
+                    Terminal::render();
+                }
+                Some(TokenListTopics::Back) => {
+                    Self::render();
+                }
+                None => panic!("Error while selecting token list screen topic"),
+            },
+            None => println!("You did not select anything"),
+        }
+    }
+
+    fn show_lists() {
+        let current_network = Network::get_current_network();
+        let sources = TokenList::get_sources(&current_network);
+
+        if sources.is_empty() {
+            println!("No token lists for {}", current_network.name);
+            return;
+        }
+
+        println!();
+        for source in sources {
+            let status = if source.enabled {
+                style("enabled").green()
+            } else {
+                style("disabled").red()
+            };
+
+            if source.trusted {
+                println!("{} [{}] [trusted]", source, status);
+            } else {
+                println!("{} [{}]", source, status);
+            }
+
+            let mut sp = Spinner::new(Spinners::Aesthetic, "Validating token list...".into());
+
+            let loaded_list = TokenList::validate(&source, current_network.chain_id);
+
+            sp.stop_with_newline();
+
+            match loaded_list {
+                Ok(loaded_list) => println!(
+                    "  {} {}, {} tokens on this network, {} invalid tokens skipped",
+                    loaded_list.name,
+                    loaded_list.version,
+                    loaded_list.tokens.len(),
+                    loaded_list.invalid_tokens
+                ),
+                Err(err) => println!("  {}", style(err).red()),
+            }
+        }
+        println!();
+    }
+
+    fn add_list() {
+        let current_network = Network::get_current_network();
+
+        let location: String = Input::new()
+            .with_prompt("Token list URL or file path")
             .interact_text()
             .unwrap();
 
-        let chain_id: u32 = Input::new()
-            .with_prompt("Token Chain Id")
-            .interact_text()
+        let mut source = TokenListSource {
+            name: String::default(),
+            location: location.trim().to_owned(),
+            enabled: true,
+            trusted: false,
+        };
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Validating token list...".into());
+
+        let loaded_list = TokenList::validate(&source, current_network.chain_id);
+
+        sp.stop_with_newline();
+
+        let loaded_list = match loaded_list {
+            Ok(loaded_list) => loaded_list,
+            Err(err) => {
+                println!("{}", style(err).red());
+                return;
+            }
+        };
+
+        println!(
+            "{} {}, {} tokens on {}, {} invalid tokens skipped",
+            loaded_list.name,
+            loaded_list.version,
+            loaded_list.tokens.len(),
+            current_network.name,
+            loaded_list.invalid_tokens
+        );
+
+        if loaded_list.tokens.is_empty() {
+            println!(
+                "{}",
+                style("Warning: list has no tokens for current network").red()
+            );
+        }
+
+        let mut sources = TokenList::get_sources(&current_network);
+
+        if sources
+            .iter()
+            .any(|stored| stored.location == source.location)
+        {
+            println!("Token list already added");
+            return;
+        }
+
+        source.trusted = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(
+                "Do you trust this list? Tokens from untrusted lists are marked in token selection",
+            )
+            .default(false)
+            .interact()
+            .unwrap();
+
+        source.name = loaded_list.name;
+        sources.push(source);
+
+        TokenList::set_sources(current_network.chain_id, sources);
+
+        println!("{}", style("Token list successfully added!").green());
+    }
+
+    fn enable_lists() {
+        let current_network = Network::get_current_network();
+        let mut sources = TokenList::get_sources(&current_network);
+
+        if sources.is_empty() {
+            println!("No token lists for {}", current_network.name);
+            return;
+        }
+
+        let defaults: Vec<bool> = sources.iter().map(|source| source.enabled).collect();
+
+        let selection = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Enabled token lists (space to toggle)")
+            .items(&sources)
+            .defaults(&defaults)
+            .interact_on_opt(&Term::stderr())
             .unwrap();
 
+        if let Some(selected) = selection {
+            for (index, source) in sources.iter_mut().enumerate() {
+                source.enabled = selected.contains(&index);
+            }
+
+            TokenList::set_sources(current_network.chain_id, sources);
+
+            println!("{}", style("Token lists updated!").green());
+        }
+    }
+
+    fn remove_list() {
+        let current_network = Network::get_current_network();
+        let mut sources = TokenList::get_sources(&current_network);
+
+        if sources.is_empty() {
+            println!("No token lists for {}", current_network.name);
+            return;
+        }
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&sources)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        if let Some(selected) = selection {
+            sources.remove(selected);
+
+            TokenList::set_sources(current_network.chain_id, sources);
+
+            println!("{}", style("Token list successfully removed!").green());
+        }
+    }
+
+    // @dev name, symbol and decimals are read from chain, user types only what token doesn't expose
+    fn add_token() -> Option<Token> {
         let address: String = Input::new()
             .with_prompt("Token Address")
+            .validate_with(|input: &String| -> Result<(), &str> {
+                AccountWallet::parse_address(input).map(|_| ())
+            })
             .interact_text()
             .unwrap();
 
-        let decimals: u32 = Input::new()
-            .with_prompt("Token Decimals")
+        let address = AccountWallet::parse_address(&address).unwrap();
+
+        let chain_id: u32 = Input::new()
+            .with_prompt("Token Chain Id")
+            .default(Network::get_current_network().chain_id)
             .interact_text()
             .unwrap();
 
-        TokenStorage::save_token(address, chain_id, decimals, name, symbol)
+        let network = match Network::get_network(chain_id) {
+            Some(network) => Arc::new(network),
+            None => {
+                println!("Chain id {} is not supported", chain_id);
+                return None;
+            }
+        };
+
+        let mut sp = Spinner::new(Spinners::Aesthetic, "Getting token metadata...".into());
+
+        let metadata = Token::get_token_metadata(address, network.clone());
+
+        sp.stop_with_newline();
+
+        let metadata = match metadata {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                println!("Error while getting token metadata: {}", err);
+                return None;
+            }
+        };
+
+        for warning in &metadata.warnings {
+            println!("{} {}", style("Warning:").red().bold(), warning);
+        }
+
+        let name = match metadata.name {
+            Some(name) => name,
+            None => Input::new()
+                .with_prompt("Token Name")
+                .interact_text()
+                .unwrap(),
+        };
+
+        let symbol = match metadata.symbol {
+            Some(symbol) => symbol,
+            None => Input::new()
+                .with_prompt("Token Symbol")
+                .interact_text()
+                .unwrap(),
+        };
+
+        let decimals = match metadata.decimals {
+            Some(decimals) => decimals as u32,
+            None => Input::new()
+                .with_prompt("Token Decimals")
+                .interact_text()
+                .unwrap(),
+        };
+
+        println!();
+        println!("Name: {}", name);
+        println!("Symbol: {}", symbol);
+        println!("Decimals: {}", decimals);
+        println!("Network: {} (chain id: {})", network.name, network.chain_id);
+        println!();
+
+        let confirm = Confirm::new()
+            .with_prompt("Do you want to add this token?")
+            .default(metadata.warnings.is_empty())
+            .interact()
+            .unwrap();
+
+        if !confirm {
+            println!("Ok, next time");
+            return None;
+        }
+
+        Some(TokenStorage::save_token(
+            format!("{:?}", address),
+            chain_id,
+            decimals,
+            name,
+            symbol,
+        ))
     }
 
     fn remove_token() {
@@ -0,0 +1,323 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::abis::FormattedOfferWithGas;
+use crate::query::Query;
+use crate::token::Token;
+use console::{style, Term};
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use ethers::{
+    types::{H160, U256},
+    utils::{format_units, parse_units},
+};
+
+use super::query::QueryPrompt;
+
+pub struct WatchScreen {}
+
+pub enum QuoteAlert {
+    Above(U256),
+    Below(U256),
+}
+
+impl QuoteAlert {
+    pub fn is_met(&self, amount_out: U256) -> bool {
+        match self {
+            QuoteAlert::Above(threshold) => amount_out >= *threshold,
+            QuoteAlert::Below(threshold) => amount_out <= *threshold,
+        }
+    }
+}
+
+// @dev interval 0 means a new quote on every new block
+pub struct WatchOptions {
+    pub interval: u64,
+    pub alert: Option<QuoteAlert>,
+}
+
+impl WatchScreen {
+    // @dev block number is polled once per second in block mode
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn prompt_options(token_out: &Token) -> WatchOptions {
+        let interval = Input::<u64>::new()
+            .with_prompt("Refresh interval in seconds (0 for every new block)")
+            .default(0)
+            .interact_text()
+            .unwrap();
+
+        let alert_items = [
+            "No alert",
+            "Alert when output is above",
+            "Alert when output is below",
+        ];
+
+        let alert_selection = Select::with_theme(&ColorfulTheme::default())
+            .items(&alert_items)
+            .default(0)
+            .interact_on_opt(&Term::stderr())
+            .unwrap();
+
+        let alert = match alert_selection {
+            Some(1) | Some(2) => {
+                let threshold = Input::<String>::new()
+                    .with_prompt(format!("Threshold in {}", token_out.symbol))
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        parse_units(input, token_out.decimals)
+                            .map(|_| ())
+                            .map_err(|_| "Wrong amount")
+                    })
+                    .interact_text()
+                    .unwrap();
+
+                let threshold = parse_units(threshold, token_out.decimals).unwrap();
+
+                match alert_selection {
+                    Some(1) => Some(QuoteAlert::Above(threshold)),
+                    _ => Some(QuoteAlert::Below(threshold)),
+                }
+            }
+            _ => None,
+        };
+
+        WatchOptions { interval, alert }
+    }
+
+    // @dev quote is redrawn in place, route switches and alerts stay printed above it
+    pub fn watch(prompt_query: &QueryPrompt, options: &WatchOptions) {
+        let term = Term::stdout();
+
+        let token_in = prompt_query.token_in.address.parse::<H160>().unwrap();
+        let token_out = prompt_query.token_out.address.parse::<H160>().unwrap();
+
+        let tokens = Token::get_tokens();
+        let adapters = Query::get_adapters();
+
+        let format_route = |offer: &FormattedOfferWithGas| {
+            let mut route = Self::format_token(&tokens, offer.path[0]);
+
+            for (adapter, token) in offer.adapters.iter().zip(offer.path.iter().skip(1)) {
+                let adapter_name = adapters
+                    .iter()
+                    .find(|known| known.address == *adapter)
+                    .map(|known| known.name.to_owned())
+                    .unwrap_or_else(|| format!("{:?}", adapter));
+
+                route.push_str(&format!(
+                    " -[{}]-> {}",
+                    adapter_name,
+                    Self::format_token(&tokens, *token)
+                ));
+            }
+
+            route
+        };
+
+        let format_amount = |amount: U256| {
+            format!(
+                "{:.4}",
+                format_units(amount, prompt_query.token_out.decimals)
+                    .unwrap()
+                    .parse::<f64>()
+                    .unwrap()
+            )
+        };
+
+        println!(
+            "Watching {} {} -> {}, {} (press Enter to stop)",
+            format_units(prompt_query.amount_in, prompt_query.token_in.decimals).unwrap(),
+            prompt_query.token_in.symbol,
+            prompt_query.token_out.symbol,
+            match options.interval {
+                0 => "every new block".to_owned(),
+                interval => format!("every {} seconds", interval),
+            }
+        );
+
+        let is_stopped = Self::listen_for_stop();
+
+        let mut previous: Option<FormattedOfferWithGas> = None;
+        let mut last_block = None;
+        let mut is_alert_met = false;
+        let mut drawn_lines = 0;
+
+        while !is_stopped.load(Ordering::Relaxed) {
+            let block_number = Query::get_block_number().ok();
+
+            if options.interval == 0 && block_number.is_some() && block_number == last_block {
+                thread::sleep(Self::POLL_INTERVAL);
+                continue;
+            }
+
+            last_block = block_number;
+
+            let offer = Query::find_best_path_with_gas(
+                prompt_query.amount_in,
+                token_in,
+                token_out,
+                prompt_query.max_steps,
+            );
+
+            term.clear_last_lines(drawn_lines).unwrap();
+
+            let block = block_number
+                .map(|block_number| block_number.to_string())
+                .unwrap_or_else(|| "?".to_owned());
+
+            let mut lines: Vec<String> = vec![format!("Block: {}", block)];
+
+            match offer {
+                Ok(offer) if offer.adapters.is_empty() => {
+                    lines.push(style("Yak path not found").red().to_string());
+                }
+                Ok(offer) => {
+                    let amount_out = *offer.amounts.last().unwrap();
+
+                    let mut output = format!(
+                        "Output: {} {}",
+                        style(format_amount(amount_out)).green(),
+                        prompt_query.token_out.symbol
+                    );
+                    let mut gas = format!("Gas estimate: {}", offer.gas_estimate);
+
+                    if let Some(previous) = &previous {
+                        let previous_amount_out = *previous.amounts.last().unwrap();
+
+                        output.push_str(&format!(
+                            " ({})",
+                            Self::format_change(
+                                amount_out,
+                                previous_amount_out,
+                                format_amount(amount_out.abs_diff(previous_amount_out)),
+                                true
+                            )
+                        ));
+
+                        gas.push_str(&format!(
+                            " ({})",
+                            Self::format_change(
+                                offer.gas_estimate,
+                                previous.gas_estimate,
+                                offer
+                                    .gas_estimate
+                                    .abs_diff(previous.gas_estimate)
+                                    .to_string(),
+                                false
+                            )
+                        ));
+
+                        // @dev switches stay on screen, so they can be compared later
+                        if previous.adapters != offer.adapters || previous.path != offer.path {
+                            println!(
+                                "{} block {}: {} => {}",
+                                style("Route switched at").yellow(),
+                                block,
+                                format_route(previous),
+                                style(format_route(&offer)).yellow()
+                            );
+                        }
+                    }
+
+                    if let Some(alert) = &options.alert {
+                        let is_met = alert.is_met(amount_out);
+
+                        if is_met && !is_alert_met {
+                            println!(
+                                "\x07{} block {}: output {} {}",
+                                style("Alert threshold crossed at").red().bold(),
+                                block,
+                                format_amount(amount_out),
+                                prompt_query.token_out.symbol
+                            );
+                        }
+
+                        is_alert_met = is_met;
+                    }
+
+                    lines.push(output);
+                    lines.push(format!("Route: {}", format_route(&offer)));
+                    lines.push(gas);
+
+                    previous = Some(offer);
+                }
+                Err(err) => {
+                    lines.push(
+                        style(format!("Error while getting best path: {}", err))
+                            .red()
+                            .to_string(),
+                    );
+                }
+            }
+
+            for line in &lines {
+                println!("{}", line);
+            }
+
+            drawn_lines = lines.len();
+
+            // @dev block number unavailable, new blocks can't be detected so RPC is polled after a pause
+            if options.interval == 0 && block_number.is_none() {
+                thread::sleep(Self::POLL_INTERVAL);
+            }
+
+            let mut waited = 0;
+
+            while options.interval > 0
+                && waited < options.interval
+                && !is_stopped.load(Ordering::Relaxed)
+            {
+                thread::sleep(Self::POLL_INTERVAL);
+                waited += 1;
+            }
+        }
+    }
+
+    // @dev set when user presses Enter, loops check it between polls
+    pub fn listen_for_stop() -> Arc<AtomicBool> {
+        let is_stopped = Arc::new(AtomicBool::new(false));
+
+        let stop_flag = is_stopped.clone();
+
+        // @dev read error or EOF (no TTY) doesn't stop, only an entered line does
+        thread::spawn(move || {
+            let mut line = String::new();
+
+            if let Ok(1..) = io::stdin().read_line(&mut line) {
+                stop_flag.store(true, Ordering::Relaxed);
+            }
+        });
+
+        is_stopped
+    }
+
+    // @dev green when change is good for the user, higher output or lower gas
+    fn format_change(
+        current: U256,
+        previous: U256,
+        amount: String,
+        is_higher_better: bool,
+    ) -> String {
+        let change = match current.cmp(&previous) {
+            std::cmp::Ordering::Greater => style(format!("+{}", amount)),
+            std::cmp::Ordering::Less => style(format!("-{}", amount)),
+            std::cmp::Ordering::Equal => return "no change".to_owned(),
+        };
+
+        if (current > previous) == is_higher_better {
+            change.green().to_string()
+        } else {
+            change.red().to_string()
+        }
+    }
+
+    fn format_token(tokens: &[Token], address: H160) -> String {
+        tokens
+            .iter()
+            .find(|token| token.address.parse::<H160>().ok() == Some(address))
+            .map(|token| token.symbol.to_owned())
+            .unwrap_or_else(|| format!("{:?}", address))
+    }
+}
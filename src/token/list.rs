@@ -0,0 +1,255 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+};
+
+use ethers::types::H160;
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::Cache, db::DB, network::Network, settings::Settings, token::Token};
+
+// @dev where to load a token list from, URL or local file path
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct TokenListSource {
+    pub name: String,
+    pub location: String,
+    pub enabled: bool,
+    // @dev tokens only found on untrusted lists are marked in token selection
+    #[serde(default)]
+    pub trusted: bool,
+}
+
+impl fmt::Display for TokenListSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.location)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TokenListVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl fmt::Display for TokenListVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// @dev required fields of https://uniswap.org/tokenlist.schema.json, others are ignored
+#[derive(Deserialize)]
+pub struct TokenListFile {
+    pub name: String,
+    pub timestamp: String,
+    pub version: TokenListVersion,
+    pub tokens: Vec<serde_json::Value>,
+}
+
+pub struct LoadedTokenList {
+    pub name: String,
+    pub version: TokenListVersion,
+    pub tokens: Vec<Token>,
+    // @dev tokens dropped because they don't match the schema
+    pub invalid_tokens: usize,
+}
+
+pub enum TokenListError {
+    ReqwestError(reqwest::Error),
+    IoError(std::io::Error),
+    JsonError(serde_json::Error),
+    InvalidSchema(&'static str),
+    NotCached,
+}
+
+impl From<reqwest::Error> for TokenListError {
+    fn from(err: reqwest::Error) -> TokenListError {
+        TokenListError::ReqwestError(err)
+    }
+}
+
+impl From<std::io::Error> for TokenListError {
+    fn from(err: std::io::Error) -> TokenListError {
+        TokenListError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for TokenListError {
+    fn from(err: serde_json::Error) -> TokenListError {
+        TokenListError::JsonError(err)
+    }
+}
+
+impl fmt::Display for TokenListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenListError::ReqwestError(err) => write!(f, "request error: {}", err),
+            TokenListError::IoError(err) => write!(f, "file error: {}", err),
+            TokenListError::JsonError(err) => write!(f, "not a token list: {}", err),
+            TokenListError::InvalidSchema(reason) => write!(f, "invalid token list: {}", reason),
+            TokenListError::NotCached => write!(f, "offline mode and token list is not cached"),
+        }
+    }
+}
+
+pub struct TokenList {}
+
+impl TokenList {
+    const DB_TOKEN_LISTS: &'static str = "token-lists";
+
+    fn default_sources(network: &Network) -> Vec<TokenListSource> {
+        let coingecko_ids = HashMap::from([
+            ("Avalanche", "avalanche"),
+            ("Dogechain", "dogechain"),
+            ("Optimism", "optimistic-ethereum"),
+            ("Arbitrum", "arbitrum-one"),
+            ("Aurora", "aurora"),
+        ]);
+
+        match coingecko_ids.get(&*network.short_name) {
+            Some(coingecko_id) => vec![TokenListSource {
+                name: "CoinGecko".to_owned(),
+                location: format!("https://tokens.coingecko.com/{}/all.json", coingecko_id),
+                enabled: true,
+                trusted: true,
+            }],
+            None => vec![],
+        }
+    }
+
+    // @dev defaults are used until lists for the chain are changed by user
+    pub fn get_sources(network: &Network) -> Vec<TokenListSource> {
+        let db_instance = DB.lock().unwrap();
+
+        let sources = db_instance.get::<HashMap<u32, Vec<TokenListSource>>>(Self::DB_TOKEN_LISTS);
+
+        match sources.and_then(|sources| sources.get(&network.chain_id).cloned()) {
+            Some(sources) => sources,
+            None => Self::default_sources(network),
+        }
+    }
+
+    pub fn set_sources(chain_id: u32, new_sources: Vec<TokenListSource>) {
+        let mut db_instance = DB.lock().unwrap();
+
+        let mut sources = db_instance
+            .get::<HashMap<u32, Vec<TokenListSource>>>(Self::DB_TOKEN_LISTS)
+            .unwrap_or_default();
+
+        sources.insert(chain_id, new_sources);
+
+        db_instance.set(Self::DB_TOKEN_LISTS, &sources).unwrap();
+    }
+
+    pub async fn load(
+        source: &TokenListSource,
+        chain_id: u32,
+    ) -> Result<LoadedTokenList, TokenListError> {
+        let content =
+            if source.location.starts_with("http://") || source.location.starts_with("https://") {
+                reqwest::get(&source.location).await?.text().await?
+            } else {
+                fs::read_to_string(&source.location)?
+            };
+
+        Self::parse(&content, chain_id)
+    }
+
+    // @dev fresh cache first, then network, then stale cache if network failed
+    pub async fn load_cached(
+        source: &TokenListSource,
+        chain_id: u32,
+    ) -> Result<Vec<Token>, TokenListError> {
+        let cache_key = format!("token-list:{}:{}", chain_id, source.location);
+
+        if let Some(tokens) = Cache::get::<Vec<Token>>(&cache_key) {
+            return Ok(tokens);
+        }
+
+        if Settings::is_offline() {
+            return Cache::get_stale::<Vec<Token>>(&cache_key).ok_or(TokenListError::NotCached);
+        }
+
+        match Self::load(source, chain_id).await {
+            Ok(loaded_list) => {
+                Cache::set(&cache_key, &loaded_list.tokens);
+
+                Ok(loaded_list.tokens)
+            }
+            Err(err) => match Cache::get_stale::<Vec<Token>>(&cache_key) {
+                Some(tokens) => {
+                    println!(
+                        "Error while getting {}, using cached tokens: {}",
+                        source.name, err
+                    );
+
+                    Ok(tokens)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    #[tokio::main]
+    pub async fn validate(
+        source: &TokenListSource,
+        chain_id: u32,
+    ) -> Result<LoadedTokenList, TokenListError> {
+        Self::load(source, chain_id).await
+    }
+
+    pub fn parse(content: &str, chain_id: u32) -> Result<LoadedTokenList, TokenListError> {
+        let list = serde_json::from_str::<TokenListFile>(content)?;
+
+        if list.name.is_empty() {
+            return Err(TokenListError::InvalidSchema("list name is empty"));
+        }
+
+        if list.timestamp.is_empty() {
+            return Err(TokenListError::InvalidSchema("list timestamp is empty"));
+        }
+
+        let total = list.tokens.len();
+
+        let tokens: Vec<Token> = list
+            .tokens
+            .into_iter()
+            .filter_map(|token| serde_json::from_value::<Token>(token).ok())
+            .filter(|token| {
+                token.address.parse::<H160>().is_ok()
+                    && token.chain_id.is_some()
+                    && token.decimals <= 255
+                    && !token.symbol.is_empty()
+            })
+            .collect();
+
+        let invalid_tokens = total - tokens.len();
+
+        Ok(LoadedTokenList {
+            name: list.name,
+            version: list.version,
+            tokens: tokens
+                .into_iter()
+                .filter(|token| token.chain_id == Some(chain_id))
+                .collect(),
+            invalid_tokens,
+        })
+    }
+
+    // @dev first occurrence of an address wins, so pass local tokens first
+    pub fn merge(token_groups: Vec<Vec<Token>>) -> Vec<Token> {
+        let mut seen: HashSet<H160> = HashSet::new();
+        let mut merged: Vec<Token> = vec![];
+
+        for token in token_groups.into_iter().flatten() {
+            let address = token.address.parse::<H160>().unwrap_or_default();
+
+            if seen.insert(address) {
+                merged.push(token);
+            }
+        }
+
+        merged
+    }
+}
@@ -1,24 +1,38 @@
 use core::fmt;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use console::style;
 use ethers::{
+    abi::{self, ParamType},
     prelude::{k256::ecdsa::SigningKey, SignerMiddleware},
-    providers::{Http, Middleware, Provider},
+    providers::{
+        call_raw::{spoof, RawCall},
+        Http, Middleware, Provider, ProviderError,
+    },
     signers::Wallet,
-    types::{BlockId, TransactionReceipt, H160, U256},
+    types::{
+        transaction::eip2718::TypedTransaction, BlockId, Bytes, TransactionReceipt,
+        TransactionRequest, H160, U256,
+    },
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{abis::ERC20, network::Network};
+use crate::{
+    abis::{YakRouter, ERC20, IWETH},
+    cache::Cache,
+    network::Network,
+    settings::Settings,
+};
+use futures::future;
+use list::{TokenList, TokenListSource};
 
 #[path = "../token/storage.rs"]
 pub mod token_storage;
 
-#[derive(Deserialize)]
-struct CoingeckoResponse {
-    tokens: Vec<Token>,
-}
+pub mod list;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -28,6 +42,9 @@ pub struct Token {
     pub decimals: u32,
     pub name: String,
     pub symbol: String,
+    // @dev set on load, true when token is on a trusted list
+    #[serde(skip)]
+    pub is_trusted: bool,
 }
 
 impl std::fmt::Display for Token {
@@ -42,104 +59,290 @@ impl std::fmt::Display for Token {
     }
 }
 
-enum ExternalTokenError {
-    NetworkNotSupported,
-    ReqwestError(reqwest::Error),
+pub enum TokenMetadataWarning {
+    NoCode,
+    MissingName,
+    MissingSymbol,
+    MissingDecimals,
+    Bytes32Name,
+    Bytes32Symbol,
 }
 
-impl From<reqwest::Error> for ExternalTokenError {
-    fn from(err: reqwest::Error) -> ExternalTokenError {
-        ExternalTokenError::ReqwestError(err)
+impl std::fmt::Display for TokenMetadataWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let warning = match self {
+            TokenMetadataWarning::NoCode => "No contract code at this address",
+            TokenMetadataWarning::MissingName => "Token has no name()",
+            TokenMetadataWarning::MissingSymbol => "Token has no symbol()",
+            TokenMetadataWarning::MissingDecimals => {
+                "Token has no decimals(), amounts will be wrong if decimals are wrong"
+            }
+            TokenMetadataWarning::Bytes32Name => "Non-standard token: name() returns bytes32",
+            TokenMetadataWarning::Bytes32Symbol => "Non-standard token: symbol() returns bytes32",
+        };
+
+        write!(f, "{}", warning)
+    }
+}
+
+pub enum WrappedNativeError {
+    NotFound(u32),
+}
+
+impl std::fmt::Display for WrappedNativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WrappedNativeError::NotFound(chain_id) => write!(
+                f,
+                "no valid wrapped native token for chain id {}, set it in network settings",
+                chain_id
+            ),
+        }
+    }
+}
+
+// @dev used by query calls which return provider errors
+impl From<WrappedNativeError> for ProviderError {
+    fn from(err: WrappedNativeError) -> ProviderError {
+        ProviderError::CustomError(err.to_string())
+    }
+}
+
+// @dev fields are None when token doesn't implement the getter
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub warnings: Vec<TokenMetadataWarning>,
+}
+
+// @dev result of a simulated transfer, standard tokens move exactly `amount`
+pub struct TransferSimulation {
+    pub amount: U256,
+    // @dev how much holder balance decreased
+    pub sent: U256,
+    // @dev how much recipient balance increased
+    pub received: U256,
+}
+
+impl TransferSimulation {
+    pub fn is_standard(&self) -> bool {
+        self.sent == self.amount && self.received == self.amount
+    }
+
+    // @dev lost part of transfer in slippage format, e.g. 5 = 0.5%, rounded up
+    pub fn fee(&self) -> u32 {
+        if self.amount.is_zero() {
+            return 0;
+        }
+
+        let lost = self
+            .amount
+            .saturating_sub(self.received)
+            .max(self.sent.saturating_sub(self.amount));
+
+        let fee = (lost * U256::from(1000) + self.amount - U256::one()) / self.amount;
+
+        fee.min(U256::from(1000)).as_u32()
     }
 }
 
 impl Token {
-    pub fn unknown() -> Self {
-        Self {
-            address: H160::zero().to_string(),
-            chain_id: Some(0),
-            decimals: 0,
-            name: "Unknown".to_owned(),
-            symbol: String::default(),
+    // @dev transfer fee in slippage format above which swaps are refused, e.g. 200 = 20%
+    pub const MAX_TRANSFER_FEE: u32 = 200;
+
+    // @dev runtime code put on holder address with eth_call state override, takes (token, recipient, amount),
+    // @dev transfers amount to recipient and returns (holder balance decrease, recipient balance increase)
+    // @dev assembled from, any failed call jumps to `fail`, transfer return data is not checked (USDT returns none):
+    //   holderBefore    := staticcall(gas(), token, balanceOf(address()))
+    //   recipientBefore := staticcall(gas(), token, balanceOf(recipient))
+    //                      call(gas(), token, 0, transfer(recipient, amount))
+    //   holderAfter     := staticcall(gas(), token, balanceOf(address()))
+    //   recipientAfter  := staticcall(gas(), token, balanceOf(recipient))
+    //   mstore(0x00, sub(holderBefore, holderAfter))
+    //   mstore(0x20, sub(recipientAfter, recipientBefore))
+    //   return(0x00, 0x40)
+    //   fail: revert(0, 0)
+    // @dev calldata is raw (token, recipient, amount) words, no selector; calls use mem[0x00..0x44] and output to mem[0x00]
+    const TRANSFER_PROBE_CODE: &str = "0x6370a0823160e01b6000523060045260206000602460006000355afa156100cf576000516370a0823160e01b60005260203560045260206000602460006000355afa156100cf5760005163a9059cbb60e01b600052602035600452604035602452602060006044600060006000355af1156100cf576370a0823160e01b6000523060045260206000602460006000355afa156100cf576000516370a0823160e01b60005260203560045260206000602460006000355afa156100cf57600051829003602052820360005260406000f35b600080fd";
+
+    // @dev configured address first, then router WAVAX(), both have to pass WETH check
+    pub async fn load_native_wrapped(network: &Network) -> Result<H160, WrappedNativeError> {
+        let cache_key = format!(
+            "wrapped-native:{}:{}:{}",
+            network.chain_id,
+            network
+                .wrapped_native
+                .to_owned()
+                .unwrap_or_default()
+                .to_lowercase(),
+            network
+                .yak_router
+                .to_owned()
+                .unwrap_or_default()
+                .to_lowercase()
+        );
+
+        if let Some(wrapped_native) = Cache::get::<H160>(&cache_key) {
+            return Ok(wrapped_native);
+        }
+
+        let configured = network
+            .wrapped_native
+            .as_ref()
+            .and_then(|address| address.parse::<H160>().ok());
+
+        // @dev can't validate without network, configured address is trusted as is
+        if Settings::is_offline() {
+            return Cache::get_stale::<H160>(&cache_key)
+                .or(configured)
+                .ok_or(WrappedNativeError::NotFound(network.chain_id));
+        }
+
+        let provider = Arc::new(
+            Provider::<Http>::try_from(network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        if let Some(configured) = configured {
+            if Self::is_native_wrapped(configured, provider.clone()).await {
+                Cache::set(&cache_key, &configured);
+
+                return Ok(configured);
+            }
+
+            println!(
+                "{}",
+                style(format!(
+                    "{:?} doesn't look like wrapped native token, trying router WAVAX()",
+                    configured
+                ))
+                .red()
+            );
         }
+
+        if let Some(yak_router) = &network.yak_router {
+            let router_contract =
+                YakRouter::new(yak_router.parse::<H160>().unwrap(), provider.clone());
+
+            if let Ok(router_wrapped) = router_contract.wavax().call().await {
+                if Self::is_native_wrapped(router_wrapped, provider).await {
+                    Cache::set(&cache_key, &router_wrapped);
+
+                    return Ok(router_wrapped);
+                }
+            }
+        }
+
+        Err(WrappedNativeError::NotFound(network.chain_id))
     }
 
-    fn supported_networks_ids() -> HashMap<&'static str, &'static str> {
-        HashMap::from([
-            ("Avalanche", "avalanche"),
-            ("Dogechain", "dogechain"),
-            ("Optimism", "optimistic-ethereum"),
-            ("Arbitrum", "arbitrum-one"),
-            ("Aurora", "aurora"),
-        ])
+    #[tokio::main]
+    pub async fn get_native_wrapped(network: Arc<Network>) -> Result<H160, WrappedNativeError> {
+        Self::load_native_wrapped(&network).await
     }
 
-    pub fn get_native_wrapped(chain_id: u32) -> H160 {
-        let wrapped_by_chain: HashMap<u32, String> = HashMap::from([
-            // @dev WAVAX
-            (
-                43114,
-                "0xb31f66aa3c1e785363f0875a1b74e27b85fd66c7".to_owned(),
-            ),
-            // @dev WDOGE
-            (
-                2000,
-                "0xb7ddc6414bf4f5515b52d8bdd69973ae205ff101".to_owned(),
-            ),
-            // @dev Arbitrum WETH (need test)
-            (
-                42161,
-                "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".to_owned(),
-            ),
-            // @dev Optimism WETH (need test)
-            (10, "0x4200000000000000000000000000000000000006".to_owned()),
-        ]);
+    #[tokio::main]
+    pub async fn is_valid_native_wrapped(address: H160, network: Arc<Network>) -> bool {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
 
-        wrapped_by_chain
-            .get(&chain_id)
-            .expect("No wrapped in chain")
-            .parse::<H160>()
-            .unwrap()
+        Self::is_native_wrapped(address, provider).await
+    }
+
+    // @dev deposit is called from token itself as it always holds native, withdraw(0) works for anyone.
+    // @dev balanceOf is checked too, eth_call with value to an EOA or payable fallback succeeds
+    async fn is_native_wrapped(address: H160, provider: Arc<Provider<Http>>) -> bool {
+        match provider.get_code(address, None).await {
+            Ok(code) if !code.is_empty() => {}
+            _ => return false,
+        }
+
+        let wrapped_contract = IWETH::new(address, provider);
+
+        let deposit = wrapped_contract
+            .deposit()
+            .value(U256::one())
+            .from(address)
+            .call()
+            .await;
+        let withdraw = wrapped_contract.withdraw(U256::zero()).call().await;
+        let balance = wrapped_contract.balance_of(H160::zero()).call().await;
+
+        deposit.is_ok() && withdraw.is_ok() && balance.is_ok()
     }
 
-    // @todo memoize
     #[tokio::main]
     pub async fn get_tokens() -> Vec<Token> {
         let cur_network = Network::get_current_network();
 
-        let coingecko_response = Self::get_external_tokens(&cur_network).await;
-        let mut coingecko_tokens: Vec<Token> = vec![];
+        let sources: Vec<TokenListSource> = TokenList::get_sources(&cur_network)
+            .into_iter()
+            .filter(|source| source.enabled)
+            .collect();
 
-        match coingecko_response {
-            Ok(tokens) => {
-                coingecko_tokens = tokens;
-            }
-            Err(err) => match err {
-                ExternalTokenError::NetworkNotSupported => {
-                    println!(
-                        "{}",
-                        style("Network not supported to get coingecko tokens, only locally added tokens shown").red()
-                    );
+        if sources.is_empty() {
+            println!(
+                "{}",
+                style("No token lists enabled for network, only locally added tokens shown").red()
+            );
+        }
+
+        let loaded_lists = future::join_all(
+            sources
+                .iter()
+                .map(|source| TokenList::load_cached(source, cur_network.chain_id)),
+        )
+        .await;
+
+        // @dev local tokens go first so they win on duplicates
+        let local_tokens = token_storage::TokenStorage::get_local_tokens();
+
+        let mut token_groups: Vec<Vec<Token>> = vec![local_tokens
+            .into_iter()
+            .filter(|token| token.chain_id == Some(cur_network.chain_id))
+            .collect()];
+
+        let mut trusted_addresses: HashSet<H160> = HashSet::new();
+
+        for (source, loaded_list) in sources.iter().zip(loaded_lists) {
+            match loaded_list {
+                Ok(tokens) => {
+                    if source.trusted {
+                        trusted_addresses.extend(
+                            tokens
+                                .iter()
+                                .filter_map(|token| token.address.parse::<H160>().ok()),
+                        );
+                    }
+
+                    token_groups.push(tokens)
                 }
-                ExternalTokenError::ReqwestError(error) => {
+                Err(err) => {
                     println!(
                         "{} {}",
-                        style("Error while getting tokens from coingecko, only locally added tokens shown").red(),
-                        error
+                        style(format!(
+                            "Error while getting tokens from {}, list skipped:",
+                            source.name
+                        ))
+                        .red(),
+                        err
                     );
                 }
-            },
+            }
         }
 
-        // @dev join tokens from external source with local tokens
-        let local_tokens = token_storage::TokenStorage::get_local_tokens();
+        let mut tokens_current_chain = TokenList::merge(token_groups);
 
-        let mut tokens_current_chain: Vec<Token> = local_tokens
-            .into_iter()
-            .filter(|token| token.chain_id == Some(cur_network.chain_id))
-            .collect();
-
-        tokens_current_chain.append(&mut coingecko_tokens);
+        for token in tokens_current_chain.iter_mut() {
+            token.is_trusted = token
+                .address
+                .parse::<H160>()
+                .map(|address| trusted_addresses.contains(&address))
+                .unwrap_or(false);
+        }
 
         let native_token = Token {
             address: "0x0000000000000000000000000000000000000000".to_owned(),
@@ -147,6 +350,7 @@ impl Token {
             decimals: 18,
             name: cur_network.currency_name,
             symbol: cur_network.currency_symbol,
+            is_trusted: true,
         };
 
         tokens_current_chain.push(native_token);
@@ -154,25 +358,200 @@ impl Token {
         tokens_current_chain
     }
 
-    async fn get_external_tokens(cur_network: &Network) -> Result<Vec<Token>, ExternalTokenError> {
-        let supported_networks_ids = Self::supported_networks_ids();
+    #[tokio::main]
+    pub async fn get_token_metadata(
+        token_address: H160,
+        network: Arc<Network>,
+    ) -> Result<TokenMetadata, ProviderError> {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let mut warnings = vec![];
+
+        let code = provider.get_code(token_address, None).await?;
 
-        let cur_network_id = supported_networks_ids.get(&*cur_network.short_name);
+        if code.is_empty() {
+            warnings.push(TokenMetadataWarning::NoCode);
 
-        if cur_network_id.is_none() {
-            return Err(ExternalTokenError::NetworkNotSupported);
+            return Ok(TokenMetadata {
+                name: None,
+                symbol: None,
+                decimals: None,
+                warnings,
+            });
         }
 
-        let external_url = format!(
-            "https://tokens.coingecko.com/{}/all.json",
-            cur_network_id.unwrap()
+        let token_contract = ERC20::new(token_address, provider.clone());
+
+        // @dev name and symbol are called raw, some tokens (e.g. MKR) return bytes32 instead of string
+        let name_call = Self::call_raw(
+            &provider,
+            token_address,
+            token_contract.name().calldata().unwrap(),
+        )
+        .await;
+        let symbol_call = Self::call_raw(
+            &provider,
+            token_address,
+            token_contract.symbol().calldata().unwrap(),
         )
-        .to_owned();
+        .await;
 
-        let response = reqwest::get(&external_url).await?;
-        let coingecko = response.json::<CoingeckoResponse>().await?;
+        let name = match Self::decode_string_or_bytes32(name_call) {
+            Some((name, false)) => Some(name),
+            Some((name, true)) => {
+                warnings.push(TokenMetadataWarning::Bytes32Name);
+                Some(name)
+            }
+            None => {
+                warnings.push(TokenMetadataWarning::MissingName);
+                None
+            }
+        };
 
-        Ok(coingecko.tokens)
+        let symbol = match Self::decode_string_or_bytes32(symbol_call) {
+            Some((symbol, false)) => Some(symbol),
+            Some((symbol, true)) => {
+                warnings.push(TokenMetadataWarning::Bytes32Symbol);
+                Some(symbol)
+            }
+            None => {
+                warnings.push(TokenMetadataWarning::MissingSymbol);
+                None
+            }
+        };
+
+        let decimals = token_contract.decimals().call().await.ok();
+
+        if decimals.is_none() {
+            warnings.push(TokenMetadataWarning::MissingDecimals);
+        }
+
+        Ok(TokenMetadata {
+            name,
+            symbol,
+            decimals,
+            warnings,
+        })
+    }
+
+    pub async fn call_raw(
+        provider: &Provider<Http>,
+        to: H160,
+        calldata: Bytes,
+    ) -> Result<Bytes, ProviderError> {
+        let tx: TypedTransaction = TransactionRequest::new().to(to).data(calldata).into();
+
+        provider.call(&tx, None).await
+    }
+
+    // @dev returns decoded value and whether it was a bytes32
+    pub fn decode_string_or_bytes32(
+        output: Result<Bytes, ProviderError>,
+    ) -> Option<(String, bool)> {
+        let output = output.ok()?;
+
+        if let Ok(decoded) = abi::decode(&[ParamType::String], &output) {
+            if let Some(value) = decoded[0].to_owned().into_string() {
+                return Some((value, false));
+            }
+        }
+
+        if output.len() == 32 {
+            let value = String::from_utf8(
+                output
+                    .iter()
+                    .take_while(|byte| **byte != 0)
+                    .cloned()
+                    .collect(),
+            )
+            .ok()?;
+
+            if !value.is_empty() {
+                return Some((value, true));
+            }
+        }
+
+        None
+    }
+
+    // @dev symbols (lowercase) shared by more than one token, scam clones usually copy symbol
+    pub fn get_duplicate_symbols(tokens: &[Token]) -> HashSet<String> {
+        let mut symbol_count: HashMap<String, usize> = HashMap::new();
+
+        for token in tokens {
+            *symbol_count.entry(token.symbol.to_lowercase()).or_default() += 1;
+        }
+
+        symbol_count
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(symbol, _)| symbol)
+            .collect()
+    }
+
+    // @dev holder needs balance of at least `amount`, nothing is sent, it's only eth_call.
+    // @dev state override is not supported by every RPC, error is returned then
+    #[tokio::main]
+    pub async fn simulate_transfer(
+        token_address: H160,
+        holder: H160,
+        amount: U256,
+        current_network: Arc<Network>,
+    ) -> Result<TransferSimulation, ProviderError> {
+        let provider = Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+            .expect("could not instantiate HTTP Provider");
+
+        // @dev any address without special treatment from token, e.g. not a pair or fee excluded
+        let recipient = H160::repeat_byte(0x42);
+
+        let mut state = spoof::state();
+        state
+            .account(holder)
+            .code(Self::TRANSFER_PROBE_CODE.parse::<Bytes>().unwrap());
+
+        let calldata = abi::encode(&[
+            abi::Token::Address(token_address),
+            abi::Token::Address(recipient),
+            abi::Token::Uint(amount),
+        ]);
+
+        let tx: TypedTransaction = TransactionRequest::new().to(holder).data(calldata).into();
+
+        let output = provider.call_raw(&tx).state(&state).await?;
+
+        let decoded = abi::decode(&[ParamType::Uint(256), ParamType::Uint(256)], &output)
+            .map_err(|_| ProviderError::CustomError("Transfer simulation failed".to_owned()))?;
+
+        Ok(TransferSimulation {
+            amount,
+            sent: decoded[0].to_owned().into_uint().unwrap(),
+            received: decoded[1].to_owned().into_uint().unwrap(),
+        })
+    }
+
+    // @dev token by address or symbol, symbol has to be unique among loaded tokens
+    pub fn find(tokens: &[Token], input: &str) -> Result<Token, &'static str> {
+        if let Ok(address) = input.parse::<H160>() {
+            return tokens
+                .iter()
+                .find(|token| token.address.parse::<H160>().ok() == Some(address))
+                .cloned()
+                .ok_or("Token address not found in token lists");
+        }
+
+        let matches: Vec<&Token> = tokens
+            .iter()
+            .filter(|token| token.symbol.eq_ignore_ascii_case(input))
+            .collect();
+
+        match matches.as_slice() {
+            [token] => Ok((*token).to_owned()),
+            [] => Err("Token symbol not found in token lists"),
+            _ => Err("Several tokens use this symbol, use token address"),
+        }
     }
 
     pub fn is_native(address: H160) -> bool {
@@ -245,6 +624,23 @@ impl Token {
             .expect("Error while getting confirmations on approve")
     }
 
+    // @dev approve call without signer, used for offline signing
+    pub fn build_approve_tx(
+        spender: H160,
+        value: U256,
+        token_address: H160,
+        current_network: Arc<Network>,
+    ) -> TypedTransaction {
+        let provider = Arc::new(
+            Provider::<Http>::try_from(current_network.rpc_url.to_owned())
+                .expect("could not instantiate HTTP Provider"),
+        );
+
+        let token_contract = ERC20::new(token_address, provider);
+
+        token_contract.approve(spender, value).tx
+    }
+
     #[tokio::main]
     pub async fn get_native_balance(current_address: H160, current_network: Arc<Network>) -> U256 {
         let provider = Arc::new(
@@ -257,3 +653,69 @@ impl Token {
         provider.get_balance(current_address, blk).await.unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: &str, symbol: &str) -> Token {
+        Token {
+            address: address.to_owned(),
+            chain_id: Some(43114),
+            decimals: 18,
+            name: symbol.to_owned(),
+            symbol: symbol.to_owned(),
+            is_trusted: true,
+        }
+    }
+
+    fn tokens() -> Vec<Token> {
+        vec![
+            token("0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7", "WAVAX"),
+            token("0xB97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E", "USDC"),
+            token("0xA7D7079b0FEaD91F3e65f86E8915Cb59c1a4C664", "USDC.e"),
+            token("0xc7198437980c041c805A1EDcbA50c1Ce5db95118", "USDT.e"),
+            token("0x9702230A8Ea53601f5cD2dc00fDBc13d4dF4A8c7", "USDt"),
+        ]
+    }
+
+    #[test]
+    fn find_by_address_ignores_checksum() {
+        let found = Token::find(&tokens(), "0xb31f66aa3c1e785363f0875a1b74e27b85fd66c7").unwrap();
+
+        assert_eq!(found.symbol, "WAVAX");
+    }
+
+    #[test]
+    fn find_by_symbol_is_case_insensitive() {
+        let found = Token::find(&tokens(), "wavax").unwrap();
+
+        assert_eq!(found.address, "0xB31f66AA3C1e785363F0875A1B74E27b85FD66c7");
+    }
+
+    #[test]
+    fn find_by_symbol_does_not_match_prefix() {
+        let found = Token::find(&tokens(), "USDC").unwrap();
+
+        assert_eq!(found.symbol, "USDC");
+    }
+
+    #[test]
+    fn find_rejects_unknown_and_ambiguous_input() {
+        let mut tokens = tokens();
+        tokens.push(token("0x0000000000000000000000000000000000000002", "USDT"));
+
+        assert_eq!(
+            Token::find(&tokens, "0x0000000000000000000000000000000000000001").err(),
+            Some("Token address not found in token lists")
+        );
+        assert_eq!(
+            Token::find(&tokens, "JOE").err(),
+            Some("Token symbol not found in token lists")
+        );
+        assert_eq!(
+            Token::find(&tokens, "usdt").err(),
+            Some("Several tokens use this symbol, use token address")
+        );
+    }
+}
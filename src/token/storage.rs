@@ -41,6 +41,7 @@ impl TokenStorage {
             decimals,
             name,
             symbol,
+            is_trusted: false,
         };
 
         db_instance.ladd(TokenStorage::DB_TOKENS_LIST, &token);
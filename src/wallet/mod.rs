@@ -12,6 +12,8 @@ use ethers::prelude::k256::ecdsa::SigningKey;
 use ethers::{
     prelude::rand::{self, RngCore},
     signers::{LocalWallet, Signer, Wallet},
+    types::H160,
+    utils::to_checksum,
 };
 use lazy_static::lazy_static;
 
@@ -37,6 +39,24 @@ impl Constants for AccountWallet {
 }
 
 impl AccountWallet {
+    // @dev mixed case input must match EIP-55 checksum, all lower or upper case is accepted as is
+    pub fn parse_address(input: &str) -> Result<H160, &'static str> {
+        let address = input
+            .trim()
+            .parse::<H160>()
+            .map_err(|_| "This is not a valid address")?;
+
+        let hex_part = input.trim().trim_start_matches("0x");
+        let is_single_case =
+            hex_part == hex_part.to_lowercase() || hex_part == hex_part.to_uppercase();
+
+        if !is_single_case && to_checksum(&address, None).trim_start_matches("0x") != hex_part {
+            return Err("Address checksum is invalid");
+        }
+
+        Ok(address)
+    }
+
     pub fn new(name: String, pk: String, password: String) -> Self {
         fs::create_dir_all(AccountWallet::PATH_KEYS).unwrap();
 
@@ -129,7 +149,7 @@ impl AccountWallet {
         println!("Wallets:");
 
         for wallet in wallets {
-            println!("Name: {} \t Address: {}", wallet.name, wallet.address);
+            println!("{}", wallet);
         }
         println!();
     }
@@ -150,7 +170,7 @@ impl AccountWallet {
 
         if let Some(selected) = selection {
             let selected_wallet = items[selected].to_owned();
-            WalletStorage::set_current_wallet(&selected_wallet.name, selected_wallet.address);
+            WalletStorage::set_current_wallet(&selected_wallet);
         } else {
             panic!("Wallet not selected");
         }
@@ -8,10 +8,21 @@ use serde::{Deserialize, Serialize};
 pub struct WalletStorage {
     pub name: String,
     pub address: H160,
+    // @dev watch-only entries have no keystore, only label and address
+    #[serde(default)]
+    pub watch_only: bool,
 }
 
 impl fmt::Display for WalletStorage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.watch_only {
+            return write!(
+                f,
+                "Name: {} \t Address: {} (watch-only)",
+                self.name, self.address
+            );
+        }
+
         write!(f, "Name: {} \t Address: {}", self.name, self.address)
     }
 }
@@ -43,42 +54,40 @@ impl WalletStorage {
         db_instance.get::<WalletStorage>(WalletStorage::DB_CURRENT_WALLET)
     }
 
-    pub fn set_current_wallet(name: &str, address: H160) {
+    pub fn set_current_wallet(wallet: &WalletStorage) {
         let mut db_instance = DB.lock().unwrap();
 
         db_instance
-            .set(
-                WalletStorage::DB_CURRENT_WALLET,
-                &WalletStorage {
-                    name: name.to_owned(),
-                    address,
-                },
-            )
+            .set(WalletStorage::DB_CURRENT_WALLET, wallet)
             .unwrap();
     }
 
     pub fn save_wallet(name: &str, address: H160) {
+        Self::save(WalletStorage {
+            name: name.to_owned(),
+            address,
+            watch_only: false,
+        });
+    }
+
+    pub fn save_watch_only(label: &str, address: H160) {
+        Self::save(WalletStorage {
+            name: label.to_owned(),
+            address,
+            watch_only: true,
+        });
+    }
+
+    fn save(wallet: WalletStorage) {
         let mut db_instance = DB.lock().unwrap();
         if !db_instance.lexists(WalletStorage::DB_WALLETS_LIST) {
             db_instance.lcreate(WalletStorage::DB_WALLETS_LIST).unwrap();
             db_instance
-                .set(
-                    WalletStorage::DB_CURRENT_WALLET,
-                    &WalletStorage {
-                        name: name.to_owned(),
-                        address,
-                    },
-                )
+                .set(WalletStorage::DB_CURRENT_WALLET, &wallet)
                 .unwrap();
         }
 
-        db_instance.ladd(
-            WalletStorage::DB_WALLETS_LIST,
-            &WalletStorage {
-                name: name.to_owned(),
-                address,
-            },
-        );
+        db_instance.ladd(WalletStorage::DB_WALLETS_LIST, &wallet);
     }
 
     pub fn remove_wallet(wallet: WalletStorage) {
@@ -101,9 +110,18 @@ impl WalletStorage {
             db_instance.rem(WalletStorage::DB_CURRENT_WALLET).unwrap();
         }
 
+        // @dev entries saved before watch-only existed serialize differently, so match by address and name
+        let wallets: Vec<WalletStorage> = db_instance
+            .liter(WalletStorage::DB_WALLETS_LIST)
+            .filter_map(|wallet_item| wallet_item.get_item::<WalletStorage>())
+            .filter(|saved| saved.address != wallet.address || saved.name != wallet.name)
+            .collect();
+
         // remove wallet from list
         db_instance
-            .lrem_value(WalletStorage::DB_WALLETS_LIST, &wallet)
+            .lrem_list(WalletStorage::DB_WALLETS_LIST)
             .unwrap();
+        db_instance.lcreate(WalletStorage::DB_WALLETS_LIST).unwrap();
+        db_instance.lextend(WalletStorage::DB_WALLETS_LIST, &wallets);
     }
 }